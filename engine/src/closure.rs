@@ -0,0 +1,58 @@
+use yggdrasil_grammar::expr::Expr;
+
+use crate::substitution::{alpha_eq, alpha_eq_term};
+
+/// The statements that close a branch, as indices into the statements on its path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Closure {
+    /// A statement and its negation, like `P` and `¬P`
+    Contradictory(usize, usize),
+
+    /// A statement that can never be true on its own, like `⊥`, `¬⊤`, or `a ≠ a`
+    SelfContradictory(usize),
+}
+
+impl Closure {
+    /// Every statement involved in closing the branch
+    pub fn statements(&self) -> Vec<usize> {
+        match self {
+            Self::Contradictory(a, b) => vec![*a, *b],
+            Self::SelfContradictory(a) => vec![*a],
+        }
+    }
+}
+
+/// Checks whether a single statement can never be true
+pub fn is_self_contradictory(expr: &Expr) -> bool {
+    match expr {
+        Expr::Contradiction => true,
+        Expr::Not(inner) => match inner.as_ref() {
+            Expr::Tautology => true,
+            Expr::Equals(left, right) => alpha_eq_term(left, right),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Checks whether one statement is the negation of the other
+pub fn is_contradictory_pair(a: &Expr, b: &Expr) -> bool {
+    let negates = |a: &Expr, b: &Expr| matches!(a, Expr::Not(a) if alpha_eq(a, b));
+    negates(a, b) || negates(b, a)
+}
+
+/// Finds the first point where the statements on a root-to-leaf path contradict each other
+pub fn find_closure<'a>(path: impl IntoIterator<Item = &'a Expr>) -> Option<Closure> {
+    let path = path.into_iter().collect::<Vec<_>>();
+
+    path.iter().enumerate().find_map(|(j, b)| {
+        if is_self_contradictory(b) {
+            Some(Closure::SelfContradictory(j))
+        } else {
+            path[..j]
+                .iter()
+                .position(|a| is_contradictory_pair(a, b))
+                .map(|i| Closure::Contradictory(i, j))
+        }
+    })
+}
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use yggdrasil_grammar::expr::{
+    constantexpr::{Constant, ConstantExpr},
+    Expr,
+};
+
+use crate::{
+    rules::{
+        identity::{identity_sides, is_reflexive, replace_identical},
+        quantifier::QuantifierRule,
+        rule_for, Rule,
+    },
+    substitution::{alpha_eq, substitute},
+    tree::{BranchId, NodeId, Tree},
+    util::expr_constants,
+};
+
+/// What a finished tree says about the statements at its root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Every branch is closed, so the root statements can't all be true together
+    Valid,
+
+    /// This branch is open and has nothing left to decompose, so the root statements can all be
+    /// true together
+    Invalid(BranchId),
+
+    /// Some open branch still has statements that need to be decomposed
+    Incomplete,
+}
+
+/// A statement that has not been fully decomposed on some open branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undecomposed {
+    pub branch: BranchId,
+    pub node: NodeId,
+}
+
+/// The result of [check_completeness]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeReport {
+    pub verdict: Verdict,
+    pub undecomposed: Vec<Undecomposed>,
+}
+
+/// Decides whether a tree is finished: either every branch is closed, or some open branch has
+/// every statement on it decomposed, with universals instantiated for every constant on the branch
+pub fn check_completeness(tree: &Tree) -> TreeReport {
+    let mut undecomposed = Vec::new();
+    let mut complete_open_branch = None;
+    let mut all_closed = true;
+
+    for leaf in tree.leaves() {
+        if tree.closure(leaf.id).is_some() {
+            continue;
+        }
+        all_closed = false;
+
+        let path = tree.path(leaf.id);
+        let exprs = path.iter().map(|v| &tree.node(*v).expr).collect::<Vec<_>>();
+        let constants = branch_constants(exprs.iter().copied());
+
+        let before = undecomposed.len();
+        undecomposed.extend(
+            path.iter()
+                .filter(|v| !is_decomposed(&tree.node(**v).expr, &exprs, &constants))
+                .map(|node| Undecomposed {
+                    branch: leaf.id,
+                    node: *node,
+                }),
+        );
+
+        if undecomposed.len() == before && complete_open_branch.is_none() {
+            complete_open_branch = Some(leaf.id);
+        }
+    }
+
+    let verdict = match (all_closed, complete_open_branch) {
+        (true, _) => Verdict::Valid,
+        (false, Some(branch)) => Verdict::Invalid(branch),
+        (false, None) => Verdict::Incomplete,
+    };
+
+    TreeReport {
+        verdict,
+        undecomposed,
+    }
+}
+
+/// Every constant used in a list of statements
+pub fn branch_constants<'a>(exprs: impl IntoIterator<Item = &'a Expr>) -> HashSet<Constant> {
+    exprs.into_iter().flat_map(expr_constants).collect()
+}
+
+/// Checks whether `expr` has been fully decomposed by the statements in `path`.
+///
+/// Statements that no rule decomposes still need every identity on the path substituted into
+/// them, except where that just gives an identity like `a = a`.
+pub fn is_decomposed(expr: &Expr, path: &[&Expr], constants: &HashSet<Constant>) -> bool {
+    let on_path = |expr: &Expr| path.iter().any(|v| alpha_eq(v, expr));
+
+    match rule_for(expr) {
+        None => path
+            .iter()
+            .filter_map(|v| identity_sides(v).ok())
+            .all(|(left, right)| {
+                [(left, right), (right, left)]
+                    .into_iter()
+                    .all(|(from, to)| {
+                        let result = replace_identical(expr, from, to);
+                        is_reflexive(&result) || on_path(&result)
+                    })
+            }),
+        Some(Rule::Stack(rule)) => rule
+            .decompose(expr)
+            .is_ok_and(|v| v.iter().all(|v| on_path(v))),
+        Some(Rule::Branch(rule)) => rule.decompose(expr).is_ok_and(|(one, two)| {
+            one.iter().all(|v| on_path(v)) || two.iter().all(|v| on_path(v))
+        }),
+        Some(Rule::Quantifier(QuantifierRule::UniversalInstantiation)) => {
+            let Expr::Universal { iter, expr } = expr else {
+                unreachable!()
+            };
+
+            // with no constants on the branch, it needs to be instantiated with a new one
+            !constants.is_empty()
+                && constants
+                    .iter()
+                    .all(|c| on_path(&substitute(expr, iter, &ConstantExpr::Constant(c.clone()))))
+        }
+        Some(Rule::Quantifier(QuantifierRule::ExistentialInstantiation)) => {
+            let Expr::Existential { iter, expr } = expr else {
+                unreachable!()
+            };
+
+            constants
+                .iter()
+                .any(|c| on_path(&substitute(expr, iter, &ConstantExpr::Constant(c.clone()))))
+        }
+        Some(Rule::Quantifier(rule)) => rule.decompose(expr, None, []).is_ok_and(|v| on_path(&v)),
+    }
+}
@@ -0,0 +1,252 @@
+use yggdrasil_grammar::expr::{constantexpr::ConstantExpr, Expr};
+
+use crate::{
+    error::EvaluationError,
+    eval::{Object, Structure},
+    tree::{BranchId, Tree},
+    util::tuples,
+    EngineResult,
+};
+
+/// Reads a [Structure] off the statements on the path to `branch`, and checks that it makes every
+/// statement on the path true.
+///
+/// Objects in the domain are named after one of the terms that refer to them, and terms that are
+/// said to be identical refer to the same object, as do functions applied to identical terms.
+/// Numbers refer to themselves. Function values that the branch doesn't mention are the first
+/// object in the domain.
+///
+/// Fails if the structure doesn't satisfy the branch, which can happen when the branch uses
+/// arithmetic or says a number is identical to something else, since the tree rules know nothing
+/// about numbers, or when a universal was never instantiated with a function term.
+pub fn countermodel(tree: &Tree, branch: BranchId) -> EngineResult<Structure> {
+    let path = tree
+        .path(branch)
+        .into_iter()
+        .map(|v| &tree.node(v).expr)
+        .collect::<Vec<_>>();
+
+    let mut terms = Vec::new();
+    for expr in &path {
+        ground_terms(expr, &mut terms);
+    }
+
+    // every term starts out as its own object, then identical ones are merged, along with function
+    // terms whose arguments have been merged
+    let mut parent = (0..terms.len()).collect::<Vec<_>>();
+    let find = |parent: &[usize], mut i: usize| {
+        while parent[i] != i {
+            i = parent[i];
+        }
+        i
+    };
+    let index = |term: &ConstantExpr| terms.iter().position(|v| v == term);
+
+    for expr in &path {
+        if let Expr::Equals(left, right) = expr {
+            let arithmetic = |term: &ConstantExpr| {
+                matches!(term, ConstantExpr::Number(_) | ConstantExpr::Operator(..))
+            };
+            if arithmetic(left) || arithmetic(right) {
+                continue;
+            }
+            if let (Some(i), Some(j)) = (index(left), index(right)) {
+                let (i, j) = (find(&parent, i), find(&parent, j));
+                parent[i] = j;
+            }
+        }
+    }
+
+    loop {
+        let same = |parent: &[usize], a: &ConstantExpr, b: &ConstantExpr| match (index(a), index(b))
+        {
+            (Some(i), Some(j)) => find(parent, i) == find(parent, j),
+            _ => a == b,
+        };
+        let congruent = (0..terms.len())
+            .flat_map(|i| (i + 1..terms.len()).map(move |j| (i, j)))
+            .find(|(i, j)| match (&terms[*i], &terms[*j]) {
+                (
+                    ConstantExpr::Function { func, args },
+                    ConstantExpr::Function {
+                        func: other_func,
+                        args: other_args,
+                    },
+                ) => {
+                    func == other_func
+                        && args.len() == other_args.len()
+                        && find(&parent, *i) != find(&parent, *j)
+                        && args
+                            .iter()
+                            .zip(other_args)
+                            .all(|(a, b)| same(&parent, a, b))
+                }
+                _ => false,
+            });
+
+        match congruent {
+            Some((i, j)) => {
+                let (i, j) = (find(&parent, i), find(&parent, j));
+                parent[i] = j;
+            }
+            None => break,
+        }
+    }
+
+    // each object is named after the first of its terms in alphabetical order
+    let names = (0..terms.len())
+        .map(|i| {
+            (0..terms.len())
+                .filter(|j| find(&parent, *j) == find(&parent, i))
+                .map(|j| term_name(&terms[j]))
+                .min()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let object = |term: &ConstantExpr| {
+        // arithmetic on numbers means the same thing in every structure
+        if let Ok(value @ Object::Number(_)) = Structure::default().eval_term(term) {
+            return value;
+        }
+
+        Object::Named(index(term).map_or_else(|| term_name(term), |i| names[i].clone()))
+    };
+
+    let mut model = Structure::default();
+    for term in &terms {
+        model.domain.insert(object(term));
+        match term {
+            ConstantExpr::Constant(constant) => {
+                model.constants.insert(constant.0.clone(), object(term));
+            }
+            ConstantExpr::Function { func, args } => {
+                model
+                    .functions
+                    .entry(func.0.clone())
+                    .or_default()
+                    .insert(args.iter().map(object).collect(), object(term));
+            }
+            _ => {}
+        }
+    }
+
+    if model.domain.is_empty() {
+        model.domain.insert(Object::Named("a".to_string()));
+    }
+
+    // fill in the rest of each function so it is defined everywhere
+    let default = model.domain.first().unwrap().clone();
+    for values in model.functions.values_mut() {
+        let arity = values.keys().next().map_or(0, |v| v.len());
+        for args in tuples(&model.domain, arity) {
+            values.entry(args).or_insert_with(|| default.clone());
+        }
+    }
+
+    for expr in &path {
+        let (expr, value) = match expr {
+            Expr::Not(inner) => (inner.as_ref(), false),
+            v => (*v, true),
+        };
+
+        match expr {
+            Expr::Literal(literal) => {
+                model.atoms.insert(literal.0.clone(), value);
+            }
+            Expr::Predicate { pred, args } => {
+                let extension = model.predicates.entry(pred.0.clone()).or_default();
+                if value && args.iter().all(is_ground) {
+                    extension.insert(args.iter().map(object).collect());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for expr in path {
+        if !model.eval(expr)? {
+            Err(EvaluationError::NotSatisfied(expr.to_string()))?
+        }
+    }
+
+    Ok(model)
+}
+
+/// Checks that every premise is true and the conclusion is false in `model`
+pub fn is_countermodel(
+    model: &Structure,
+    premises: &[Expr],
+    conclusion: &Expr,
+) -> EngineResult<bool> {
+    for premise in premises {
+        if !model.eval(premise)? {
+            return Ok(false);
+        }
+    }
+    Ok(!model.eval(conclusion)?)
+}
+
+/// Collects every term without variables in a statement, inner terms first
+fn ground_terms(expr: &Expr, terms: &mut Vec<ConstantExpr>) {
+    fn visit(term: &ConstantExpr, terms: &mut Vec<ConstantExpr>) {
+        match term {
+            ConstantExpr::Function { args, .. } => args.iter().for_each(|v| visit(v, terms)),
+            ConstantExpr::Operator(_, left, right) => {
+                visit(left, terms);
+                visit(right, terms);
+            }
+            _ => {}
+        }
+
+        if is_ground(term) && !terms.contains(term) {
+            terms.push(term.clone());
+        }
+    }
+
+    match expr {
+        Expr::Predicate { args, .. } => args.iter().for_each(|v| visit(v, terms)),
+        Expr::ConstantValue(term) => visit(term, terms),
+        Expr::Equals(left, right) => {
+            visit(left, terms);
+            visit(right, terms);
+        }
+        Expr::Not(inner) => ground_terms(inner, terms),
+        Expr::And(left, right)
+        | Expr::Or(left, right)
+        | Expr::Xor(left, right)
+        | Expr::Conditional(left, right)
+        | Expr::Biconditional(left, right)
+        | Expr::UnknownOperator { left, right, .. } => {
+            ground_terms(left, terms);
+            ground_terms(right, terms);
+        }
+        Expr::Universal { expr, .. } | Expr::Existential { expr, .. } => ground_terms(expr, terms),
+        _ => {}
+    }
+}
+
+fn is_ground(term: &ConstantExpr) -> bool {
+    match term {
+        ConstantExpr::Variable(_) => false,
+        ConstantExpr::Function { args, .. } => args.iter().all(is_ground),
+        ConstantExpr::Operator(_, left, right) => is_ground(left) && is_ground(right),
+        _ => true,
+    }
+}
+
+fn term_name(term: &ConstantExpr) -> String {
+    match term {
+        ConstantExpr::Constant(constant) => constant.0.clone(),
+        ConstantExpr::Variable(v) => v.name.clone(),
+        ConstantExpr::Number(n) => n.to_string(),
+        ConstantExpr::Function { func, args } => format!(
+            "{}({})",
+            func.0,
+            args.iter().map(term_name).collect::<Vec<_>>().join(", ")
+        ),
+        ConstantExpr::Operator(op, left, right) => {
+            format!("({} {} {})", term_name(left), op, term_name(right))
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use thiserror::Error;
-use yggdrasil_grammar::expr::ExprDiscriminants;
+use yggdrasil_grammar::expr::{constantexpr::ConstantExprDiscriminants, ExprDiscriminants};
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum EngineError {
@@ -8,6 +8,9 @@ pub enum EngineError {
 
     #[error("This feature (\"{0}\") isn't supported yet")]
     NotSupported(String),
+
+    #[error("Could not evaluate statement: {0}")]
+    EvaluationError(EvaluationError),
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -19,6 +22,69 @@ pub enum ValidationError {
 
     #[error("Expected statement of type {0}, found {1}")]
     InvalidStatementType(ExprDiscriminants, ExprDiscriminants),
+
+    #[error("This rule needs a term to instantiate the quantified variable with")]
+    MissingTerm,
+
+    #[error("Statement is not a valid instance of the quantified statement")]
+    InvalidInstance,
+
+    #[error("Expected a constant to instantiate with, found {0}")]
+    ExpectedConstant(ConstantExprDiscriminants),
+
+    #[error("Constant {0} already appears on this branch")]
+    ConstantNotNew(String),
+
+    #[error("The statement this rule was applied to is not on this branch")]
+    SourceNotOnPath,
+
+    #[error("Expected an identity statement like a = b")]
+    ExpectedIdentity,
+
+    #[error("Statement is not a result of applying the {0} rule")]
+    InvalidDecomposition(String),
+
+    #[error("Branch does not start with a result of applying the {0} rule")]
+    InvalidSubBranch(String),
+
+    #[error("Only premises and the results of a branching rule can be written without a rule")]
+    MissingRule,
+
+    #[error("This branch has already been split")]
+    AlreadySplit,
+
+    #[error("Expected a statement made of sentence letters and connectives, found {0}")]
+    NotPropositional(ExprDiscriminants),
+
+    #[error("Statement has {0} sentence letters, but at most {1} are allowed")]
+    TooManyAtoms(usize, usize),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EvaluationError {
+    #[error("Constant {0} does not refer to anything in the domain")]
+    UnknownConstant(String),
+
+    #[error("Function {0} is not defined for ({1})")]
+    UndefinedFunction(String, String),
+
+    #[error("Variable {0} is not bound by an enclosing quantifier")]
+    UnboundVariable(String),
+
+    #[error("Operator {0} only works on numbers, found {1}")]
+    NotANumber(String, String),
+
+    #[error("Result of {0} is undefined or too large")]
+    ArithmeticError(String),
+
+    #[error("Operator {0} has no meaning here")]
+    UnknownOperator(String),
+
+    #[error("Statements of type {0} can't be evaluated")]
+    Unevaluable(ExprDiscriminants),
+
+    #[error("The structure read off the branch makes {0} false")]
+    NotSatisfied(String),
 }
 
 impl From<ValidationError> for EngineError {
@@ -26,3 +92,9 @@ impl From<ValidationError> for EngineError {
         EngineError::ValidationError(value)
     }
 }
+
+impl From<EvaluationError> for EngineError {
+    fn from(value: EvaluationError) -> Self {
+        EngineError::EvaluationError(value)
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use yggdrasil_grammar::expr::{constantexpr::ConstantExpr, Expr, ExprDiscriminants};
+
+use crate::{error::EvaluationError, EngineResult};
+
+/// Something a term can refer to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Object {
+    Named(String),
+    Number(isize),
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Named(name) => write!(f, "{}", name),
+            Self::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// A finite interpretation of the constants, functions, predicates and sentence letters in a
+/// statement.
+///
+/// Numbers always refer to themselves, and the arithmetic operators (`+`, `-`, `*`, `/`, `^`, `%`)
+/// and comparisons (`<`, `>`, `≤`, `≥`) have their usual meaning on them, as does the successor
+/// function `s` (written `n'`).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Structure {
+    /// The objects quantifiers range over
+    pub domain: BTreeSet<Object>,
+
+    /// The object each constant refers to
+    pub constants: BTreeMap<String, Object>,
+
+    /// The object each function maps its arguments to
+    pub functions: BTreeMap<String, BTreeMap<Vec<Object>, Object>>,
+
+    /// The tuples of objects each predicate is true of. Predicates not listed are never true.
+    pub predicates: BTreeMap<String, BTreeSet<Vec<Object>>>,
+
+    /// The truth value of each sentence letter. Sentence letters not listed are false.
+    pub atoms: BTreeMap<String, bool>,
+}
+
+impl Structure {
+    pub fn new(domain: impl IntoIterator<Item = Object>) -> Self {
+        Self {
+            domain: domain.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Finds the truth value of a statement, with quantifiers ranging over the domain
+    pub fn eval(&self, expr: &Expr) -> EngineResult<bool> {
+        self.eval_in(expr, &mut Vec::new())
+    }
+
+    /// Finds the object a term refers to
+    pub fn eval_term(&self, term: &ConstantExpr) -> EngineResult<Object> {
+        self.term_in(term, &[])
+    }
+
+    fn eval_in(&self, expr: &Expr, bound: &mut Vec<(String, Object)>) -> EngineResult<bool> {
+        Ok(match expr {
+            Expr::Literal(literal) => self.atoms.get(&literal.0).copied().unwrap_or(false),
+            Expr::Tautology => true,
+            Expr::Contradiction => false,
+            Expr::Predicate { pred, args } => {
+                let args = args
+                    .iter()
+                    .map(|v| self.term_in(v, bound))
+                    .collect::<EngineResult<Vec<_>>>()?;
+                self.predicates
+                    .get(&pred.0)
+                    .is_some_and(|v| v.contains(&args))
+            }
+            Expr::Not(inner) => !self.eval_in(inner, bound)?,
+            Expr::And(left, right) => self.eval_in(left, bound)? && self.eval_in(right, bound)?,
+            Expr::Or(left, right) => self.eval_in(left, bound)? || self.eval_in(right, bound)?,
+            Expr::Xor(left, right) => self.eval_in(left, bound)? != self.eval_in(right, bound)?,
+            Expr::Conditional(left, right) => {
+                !self.eval_in(left, bound)? || self.eval_in(right, bound)?
+            }
+            Expr::Biconditional(left, right) => {
+                self.eval_in(left, bound)? == self.eval_in(right, bound)?
+            }
+            Expr::Universal { iter, expr } => {
+                for object in &self.domain {
+                    bound.push((iter.name.clone(), object.clone()));
+                    let value = self.eval_in(expr, bound);
+                    bound.pop();
+                    if !value? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            Expr::Existential { iter, expr } => {
+                for object in &self.domain {
+                    bound.push((iter.name.clone(), object.clone()));
+                    let value = self.eval_in(expr, bound);
+                    bound.pop();
+                    if value? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            Expr::Equals(left, right) => {
+                self.term_in(left, bound)? == self.term_in(right, bound)?
+            }
+            Expr::ConstantValue(ConstantExpr::Operator(op, left, right)) => {
+                let left = self.term_in(left, bound)?;
+                let right = self.term_in(right, bound)?;
+                let (left, right) = numbers(op, &left, &right)?;
+
+                match op.as_str() {
+                    "<" => left < right,
+                    ">" => left > right,
+                    "≤" => left <= right,
+                    "≥" => left >= right,
+                    _ => Err(EvaluationError::UnknownOperator(op.clone()))?,
+                }
+            }
+            expr => Err(EvaluationError::Unevaluable(ExprDiscriminants::from(expr)))?,
+        })
+    }
+
+    fn term_in(&self, term: &ConstantExpr, bound: &[(String, Object)]) -> EngineResult<Object> {
+        Ok(match term {
+            ConstantExpr::Constant(constant) => self
+                .constants
+                .get(&constant.0)
+                .cloned()
+                .ok_or_else(|| EvaluationError::UnknownConstant(constant.0.clone()))?,
+            ConstantExpr::Variable(v) => bound
+                .iter()
+                .rev()
+                .find(|(name, _)| *name == v.name)
+                .map(|(_, object)| object.clone())
+                .ok_or_else(|| EvaluationError::UnboundVariable(v.name.clone()))?,
+            ConstantExpr::Number(n) => Object::Number(*n),
+            ConstantExpr::Function { func, args } => {
+                let args = args
+                    .iter()
+                    .map(|v| self.term_in(v, bound))
+                    .collect::<EngineResult<Vec<_>>>()?;
+                if let ("s", [Object::Number(n)]) = (func.0.as_str(), args.as_slice()) {
+                    return Ok(Object::Number(n.checked_add(1).ok_or_else(|| {
+                        EvaluationError::ArithmeticError(format!("{}'", n))
+                    })?));
+                }
+                self.functions
+                    .get(&func.0)
+                    .and_then(|v| v.get(&args))
+                    .cloned()
+                    .ok_or_else(|| {
+                        EvaluationError::UndefinedFunction(
+                            func.0.clone(),
+                            args.iter()
+                                .map(|v| v.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        )
+                    })?
+            }
+            ConstantExpr::Operator(op, left, right) => {
+                let left = self.term_in(left, bound)?;
+                let right = self.term_in(right, bound)?;
+                let (a, b) = numbers(op, &left, &right)?;
+
+                let result = match op.as_str() {
+                    "+" => a.checked_add(b),
+                    "-" => a.checked_sub(b),
+                    "*" => a.checked_mul(b),
+                    "/" => a.checked_div(b),
+                    "%" => a.checked_rem(b),
+                    "^" => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+                    _ => Err(EvaluationError::UnknownOperator(op.clone()))?,
+                };
+                Object::Number(result.ok_or_else(|| {
+                    EvaluationError::ArithmeticError(format!("{} {} {}", left, op, right))
+                })?)
+            }
+        })
+    }
+}
+
+fn numbers(op: &str, left: &Object, right: &Object) -> EngineResult<(isize, isize)> {
+    match (left, right) {
+        (Object::Number(left), Object::Number(right)) => Ok((*left, *right)),
+        (Object::Named(_), _) => Err(EvaluationError::NotANumber(
+            op.to_string(),
+            left.to_string(),
+        ))?,
+        (_, Object::Named(_)) => Err(EvaluationError::NotANumber(
+            op.to_string(),
+            right.to_string(),
+        ))?,
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+use yggdrasil_grammar::expr::{
+    constantexpr::{Constant, ConstantExpr},
+    literal::Literal,
+    Expr,
+};
+
+use crate::util::tuples;
+
+/// The symbols used in a set of statements
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Signature {
+    /// Constants and numbers, in the order they are first used
+    pub constants: Vec<ConstantExpr>,
+
+    /// The number of arguments each function takes
+    pub functions: BTreeMap<String, usize>,
+
+    /// The number of arguments each predicate takes
+    pub predicates: BTreeMap<String, usize>,
+
+    /// Sentence letters
+    pub atoms: BTreeSet<String>,
+}
+
+impl Signature {
+    /// Checks whether the Herbrand universe is finite, which it is when there are no functions
+    pub fn is_finite(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+/// Collects the symbols used in `exprs`. Arithmetic operators and `=` have a fixed meaning, so
+/// they aren't included.
+pub fn signature<'a>(exprs: impl IntoIterator<Item = &'a Expr>) -> Signature {
+    fn walk_term(term: &ConstantExpr, found: &mut Signature) {
+        match term {
+            ConstantExpr::Constant(_) | ConstantExpr::Number(_) => {
+                if !found.constants.contains(term) {
+                    found.constants.push(term.clone());
+                }
+            }
+            ConstantExpr::Function { func, args } => {
+                found.functions.insert(func.0.clone(), args.len());
+                args.iter().for_each(|v| walk_term(v, found));
+            }
+            ConstantExpr::Operator(_, left, right) => {
+                walk_term(left, found);
+                walk_term(right, found);
+            }
+            ConstantExpr::Variable(_) => (),
+        }
+    }
+
+    fn walk(expr: &Expr, found: &mut Signature) {
+        match expr {
+            Expr::Literal(literal) => {
+                found.atoms.insert(literal.0.clone());
+            }
+            Expr::Predicate { pred, args } => {
+                found.predicates.insert(pred.0.clone(), args.len());
+                args.iter().for_each(|v| walk_term(v, found));
+            }
+            Expr::ConstantValue(term) => walk_term(term, found),
+            Expr::Equals(left, right) => {
+                walk_term(left, found);
+                walk_term(right, found);
+            }
+            Expr::Not(inner) => walk(inner, found),
+            Expr::And(left, right)
+            | Expr::Or(left, right)
+            | Expr::Xor(left, right)
+            | Expr::Conditional(left, right)
+            | Expr::Biconditional(left, right)
+            | Expr::UnknownOperator { left, right, .. } => {
+                walk(left, found);
+                walk(right, found);
+            }
+            Expr::Universal { expr, .. } | Expr::Existential { expr, .. } => walk(expr, found),
+            Expr::Variable(_) | Expr::Tautology | Expr::Contradiction | Expr::Invalid => (),
+        }
+    }
+
+    let mut found = Signature::default();
+    for expr in exprs {
+        walk(expr, &mut found);
+    }
+    found
+}
+
+/// Lazily lists every ground term that can be built from a [Signature], shallowest first.
+///
+/// If there are no constants, a constant `a` is made up so that the universe isn't empty. If there
+/// are any functions, the universe is infinite and this never ends, which is why a tree with a
+/// universal and a function may never be finished.
+#[derive(Debug, Clone)]
+pub struct HerbrandUniverse {
+    functions: Vec<(String, usize)>,
+
+    /// Every term found so far, and the index in `terms` where the terms of the deepest level start
+    terms: Vec<ConstantExpr>,
+    level_start: usize,
+
+    pending: VecDeque<ConstantExpr>,
+}
+
+impl HerbrandUniverse {
+    pub fn new(signature: &Signature) -> Self {
+        let mut pending = signature.constants.iter().cloned().collect::<VecDeque<_>>();
+        if pending.is_empty() {
+            pending.push_back(ConstantExpr::Constant(Constant("a".to_string())));
+        }
+
+        Self {
+            functions: signature
+                .functions
+                .iter()
+                .map(|(name, arity)| (name.clone(), *arity))
+                .collect(),
+            terms: Vec::new(),
+            level_start: 0,
+            pending,
+        }
+    }
+
+    /// Builds every term whose deepest argument is in the last level
+    fn next_level(&mut self) {
+        let start = self.level_start;
+        self.level_start = self.terms.len();
+
+        for (func, arity) in &self.functions {
+            for args in tuples(&self.terms, *arity) {
+                if args.iter().any(|v| self.terms[start..].contains(v)) {
+                    self.pending.push_back(ConstantExpr::Function {
+                        func: Constant(func.clone()),
+                        args,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for HerbrandUniverse {
+    type Item = ConstantExpr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_empty() && self.level_start < self.terms.len() {
+            self.next_level();
+        }
+
+        let term = self.pending.pop_front()?;
+        self.terms.push(term.clone());
+        Some(term)
+    }
+}
+
+/// Lazily lists every atomic statement that can be built from a [Signature]: its sentence letters,
+/// then its predicates applied to the terms of the [HerbrandUniverse] in the order they are found.
+#[derive(Debug, Clone)]
+pub struct HerbrandBase {
+    predicates: Vec<(String, usize)>,
+    universe: HerbrandUniverse,
+    terms: Vec<ConstantExpr>,
+    pending: VecDeque<Expr>,
+}
+
+impl HerbrandBase {
+    pub fn new(signature: &Signature) -> Self {
+        Self {
+            predicates: signature
+                .predicates
+                .iter()
+                .map(|(name, arity)| (name.clone(), *arity))
+                .collect(),
+            universe: HerbrandUniverse::new(signature),
+            terms: Vec::new(),
+            pending: signature
+                .atoms
+                .iter()
+                .map(|v| Expr::Literal(Literal(v.clone())))
+                .collect(),
+        }
+    }
+}
+
+impl Iterator for HerbrandBase {
+    type Item = Expr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() && !self.predicates.is_empty() {
+            let term = self.universe.next()?;
+            self.terms.push(term.clone());
+
+            // only the atoms that use the new term haven't been listed yet
+            for (pred, arity) in &self.predicates {
+                for args in tuples(&self.terms, *arity) {
+                    if args.contains(&term) {
+                        self.pending.push_back(Expr::Predicate {
+                            pred: Literal(pred.clone()),
+                            args,
+                        });
+                    }
+                }
+            }
+        }
+
+        self.pending.pop_front()
+    }
+}
+
+/// Finds the names of every constant and function in `exprs`
+pub(crate) fn symbol_names<'a>(exprs: impl IntoIterator<Item = &'a Expr>) -> HashSet<String> {
+    let signature = signature(exprs);
+    signature
+        .constants
+        .into_iter()
+        .filter_map(|v| match v {
+            ConstantExpr::Constant(c) => Some(c.0),
+            _ => None,
+        })
+        .chain(signature.functions.into_keys())
+        .collect()
+}
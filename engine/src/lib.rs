@@ -1,7 +1,20 @@
 use error::EngineError;
 
+pub mod closure;
+pub mod completeness;
+pub mod countermodel;
 pub mod error;
+pub mod eval;
+pub mod herbrand;
+pub mod normal_form;
+pub mod prover;
 pub mod rules;
+pub mod sat;
+pub mod skolem;
+pub mod substitution;
+pub mod tree;
+pub mod truth_table;
+pub mod unify;
 pub mod util;
 
 pub type EngineResult<T = ()> = Result<T, EngineError>;
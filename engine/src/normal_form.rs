@@ -0,0 +1,461 @@
+use std::{collections::HashSet, iter};
+
+use strum::{Display, EnumIter, EnumMessage};
+use yggdrasil_grammar::expr::{
+    constantexpr::ConstantExpr, literal::Literal, variable::Variable, Expr, ExprDiscriminants,
+};
+
+use crate::{
+    error::ValidationError,
+    substitution::{free_variables, fresh_name, shift, substitute},
+    EngineResult,
+};
+
+/// A single rewrite that can be applied to part of a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumMessage, Display)]
+pub enum Transformation {
+    /// p -> q => ~p | q
+    ConditionalElimination,
+
+    /// p <-> q => (p -> q) & (q -> p)
+    BiconditionalElimination,
+
+    /// p ⊕ q => (p | q) & ~(p & q)
+    XorElimination,
+
+    /// ~~p => p
+    DoubleNegation,
+
+    /// ~(p & q) => ~p | ~q, and ~(p | q) => ~p & ~q
+    DeMorgan,
+
+    /// ~⊤ => ⊥, and ~⊥ => ⊤
+    ConstantNegation,
+
+    /// ~@x p => /x ~p, and ~/x p => @x ~p
+    QuantifierNegation,
+
+    /// p | (q & r) => (p | q) & (p | r), or the same with & and | swapped
+    Distribution,
+
+    /// Replaces a sub-statement with a new sentence letter, and adds clauses saying they are
+    /// equivalent
+    Tseitin,
+
+    /// @x p => @y p[y/x], so the quantifier can be moved without capturing anything
+    VariableRenaming,
+
+    /// (@x p) & q => @x (p & q), where x is not free in q
+    QuantifierExtraction,
+}
+
+/// One step of a [Derivation]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub transformation: Transformation,
+
+    /// The whole statement after the transformation was applied
+    pub result: Expr,
+}
+
+/// The steps taken to transform a statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Derivation {
+    pub start: Expr,
+    pub steps: Vec<Step>,
+}
+
+impl Derivation {
+    pub fn new(start: Expr) -> Self {
+        Self {
+            start,
+            steps: Vec::new(),
+        }
+    }
+
+    /// The statement after every step
+    pub fn result(&self) -> &Expr {
+        self.steps.last().map_or(&self.start, |v| &v.result)
+    }
+
+    /// Applies `rule` to the first sub-statement it works on, until it doesn't work on any
+    fn rewrite(&mut self, rule: impl Fn(&Expr) -> Option<(Transformation, Expr)>) {
+        while let Some((transformation, result)) = rewrite_once(self.result(), &rule) {
+            self.steps.push(Step {
+                transformation,
+                result,
+            });
+        }
+    }
+}
+
+/// Rewrites `->`, `<->` and `⊕` in terms of `~`, `&` and `|`
+pub fn eliminate_connectives(expr: &Expr) -> Derivation {
+    let mut derivation = Derivation::new(expr.clone());
+    derivation.rewrite(elimination);
+    derivation
+}
+
+/// Negation normal form: only `~`, `&`, `|` and quantifiers, with `~` only in front of atoms
+pub fn nnf(expr: &Expr) -> Derivation {
+    let mut derivation = eliminate_connectives(expr);
+    derivation.rewrite(negation);
+    derivation
+}
+
+/// Conjunctive normal form: a conjunction of disjunctions of atoms and negated atoms.
+/// Quantified statements are put in this form inside the quantifier.
+pub fn cnf(expr: &Expr) -> Derivation {
+    let mut derivation = nnf(expr);
+    derivation.rewrite(|v| distribution(v, true));
+    derivation
+}
+
+/// Disjunctive normal form: a disjunction of conjunctions of atoms and negated atoms.
+/// Quantified statements are put in this form inside the quantifier.
+pub fn dnf(expr: &Expr) -> Derivation {
+    let mut derivation = nnf(expr);
+    derivation.rewrite(|v| distribution(v, false));
+    derivation
+}
+
+/// Prenex normal form: every quantifier at the front, in negation normal form
+pub fn prenex(expr: &Expr) -> Derivation {
+    let mut derivation = nnf(expr);
+    derivation.rewrite(extraction);
+    derivation
+}
+
+/// A conjunctive normal form that is satisfiable exactly when `expr` is, which only grows linearly
+/// with the size of `expr`. Each compound sub-statement is replaced by a new sentence letter.
+pub fn tseitin(expr: &Expr) -> EngineResult<Derivation> {
+    let mut letters = HashSet::new();
+    sentence_letters(expr, &mut letters)?;
+
+    let mut derivation = Derivation::new(expr.clone());
+    let mut rest = expr.clone();
+    let mut clauses = Vec::new();
+
+    while let Some(sub) = innermost_compound(&rest).cloned() {
+        let name = fresh_name("T", &letters);
+        letters.insert(name.clone());
+        let letter = Expr::Literal(Literal(name));
+
+        clauses.extend(definition(&letter, &sub));
+        rest = replace(&rest, &sub, &letter);
+
+        derivation.steps.push(Step {
+            transformation: Transformation::Tseitin,
+            result: conjunction(iter::once(rest.clone()).chain(clauses.iter().cloned())),
+        });
+    }
+
+    Ok(derivation)
+}
+
+type BinaryOp = fn(Box<Expr>, Box<Expr>) -> Expr;
+
+fn rewrite_once(
+    expr: &Expr,
+    rule: &impl Fn(&Expr) -> Option<(Transformation, Expr)>,
+) -> Option<(Transformation, Expr)> {
+    if let Some(v) = rule(expr) {
+        return Some(v);
+    }
+
+    let binary = |left: &Expr, right: &Expr, make: BinaryOp| {
+        if let Some((t, left)) = rewrite_once(left, rule) {
+            Some((t, make(Box::new(left), Box::new(right.clone()))))
+        } else {
+            rewrite_once(right, rule)
+                .map(|(t, right)| (t, make(Box::new(left.clone()), Box::new(right))))
+        }
+    };
+
+    match expr {
+        Expr::Not(inner) => rewrite_once(inner, rule).map(|(t, v)| (t, Expr::Not(Box::new(v)))),
+        Expr::And(l, r) => binary(l, r, Expr::And),
+        Expr::Or(l, r) => binary(l, r, Expr::Or),
+        Expr::Xor(l, r) => binary(l, r, Expr::Xor),
+        Expr::Conditional(l, r) => binary(l, r, Expr::Conditional),
+        Expr::Biconditional(l, r) => binary(l, r, Expr::Biconditional),
+        Expr::Universal { iter, expr } => rewrite_once(expr, rule).map(|(t, v)| {
+            (
+                t,
+                Expr::Universal {
+                    iter: iter.clone(),
+                    expr: Box::new(v),
+                },
+            )
+        }),
+        Expr::Existential { iter, expr } => rewrite_once(expr, rule).map(|(t, v)| {
+            (
+                t,
+                Expr::Existential {
+                    iter: iter.clone(),
+                    expr: Box::new(v),
+                },
+            )
+        }),
+        _ => None,
+    }
+}
+
+fn not(expr: &Expr) -> Box<Expr> {
+    Box::new(Expr::Not(Box::new(expr.clone())))
+}
+
+fn elimination(expr: &Expr) -> Option<(Transformation, Expr)> {
+    match expr {
+        Expr::Conditional(l, r) => Some((
+            Transformation::ConditionalElimination,
+            Expr::Or(not(l), r.clone()),
+        )),
+        Expr::Biconditional(l, r) => Some((
+            Transformation::BiconditionalElimination,
+            Expr::And(
+                Box::new(Expr::Conditional(l.clone(), r.clone())),
+                Box::new(Expr::Conditional(r.clone(), l.clone())),
+            ),
+        )),
+        Expr::Xor(l, r) => Some((
+            Transformation::XorElimination,
+            Expr::And(
+                Box::new(Expr::Or(l.clone(), r.clone())),
+                Box::new(Expr::Not(Box::new(Expr::And(l.clone(), r.clone())))),
+            ),
+        )),
+        _ => None,
+    }
+}
+
+fn negation(expr: &Expr) -> Option<(Transformation, Expr)> {
+    let Expr::Not(inner) = expr else {
+        return None;
+    };
+
+    match inner.as_ref() {
+        Expr::Not(v) => Some((Transformation::DoubleNegation, v.as_ref().clone())),
+        Expr::And(l, r) => Some((Transformation::DeMorgan, Expr::Or(not(l), not(r)))),
+        Expr::Or(l, r) => Some((Transformation::DeMorgan, Expr::And(not(l), not(r)))),
+        Expr::Tautology => Some((Transformation::ConstantNegation, Expr::Contradiction)),
+        Expr::Contradiction => Some((Transformation::ConstantNegation, Expr::Tautology)),
+        Expr::Universal { iter, expr } => Some((
+            Transformation::QuantifierNegation,
+            Expr::Existential {
+                iter: iter.clone(),
+                expr: not(expr),
+            },
+        )),
+        Expr::Existential { iter, expr } => Some((
+            Transformation::QuantifierNegation,
+            Expr::Universal {
+                iter: iter.clone(),
+                expr: not(expr),
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Distributes `|` over `&` if `over_and`, otherwise `&` over `|`
+fn distribution(expr: &Expr, over_and: bool) -> Option<(Transformation, Expr)> {
+    // `outer` is distributed over `inner`
+    let (outer, inner): (BinaryOp, BinaryOp) = if over_and {
+        (Expr::Or, Expr::And)
+    } else {
+        (Expr::And, Expr::Or)
+    };
+    fn split(v: &Expr, or: bool) -> Option<(&Expr, &Expr)> {
+        match (v, or) {
+            (Expr::Or(l, r), true) | (Expr::And(l, r), false) => Some((l, r)),
+            _ => None,
+        }
+    }
+
+    let (left, right) = split(expr, over_and)?;
+    let rebuild = |a: &Expr, b: &Expr| Box::new(outer(Box::new(a.clone()), Box::new(b.clone())));
+
+    if let Some((a, b)) = split(right, !over_and) {
+        Some((
+            Transformation::Distribution,
+            inner(rebuild(left, a), rebuild(left, b)),
+        ))
+    } else {
+        split(left, !over_and).map(|(a, b)| {
+            (
+                Transformation::Distribution,
+                inner(rebuild(a, right), rebuild(b, right)),
+            )
+        })
+    }
+}
+
+fn extraction(expr: &Expr) -> Option<(Transformation, Expr)> {
+    let (left, right, make): (_, _, BinaryOp) = match expr {
+        Expr::And(l, r) => (l.as_ref(), r.as_ref(), Expr::And),
+        Expr::Or(l, r) => (l.as_ref(), r.as_ref(), Expr::Or),
+        _ => return None,
+    };
+
+    let quantified = |v: &Expr| match v {
+        Expr::Universal { iter, expr } | Expr::Existential { iter, expr } => {
+            Some((iter.clone(), expr.as_ref().clone()))
+        }
+        _ => None,
+    };
+    let requantify = |like: &Expr, iter: Variable, expr: Expr| match like {
+        Expr::Universal { .. } => Expr::Universal {
+            iter,
+            expr: Box::new(expr),
+        },
+        _ => Expr::Existential {
+            iter,
+            expr: Box::new(expr),
+        },
+    };
+
+    let (quantifier, other, on_left) = if quantified(left).is_some() {
+        (left, right, true)
+    } else if quantified(right).is_some() {
+        (right, left, false)
+    } else {
+        return None;
+    };
+    let (iter, body) = quantified(quantifier).unwrap();
+
+    // rename the variable first if moving the quantifier would capture something in `other`
+    let other_vars = free_variables(other);
+    if other_vars.contains(&iter.name) {
+        let mut avoid = other_vars;
+        avoid.extend(free_variables(&body));
+        let renamed = Variable {
+            name: fresh_name(&iter.name, &avoid),
+            ..iter.clone()
+        };
+        let body = substitute(&body, &iter, &ConstantExpr::Variable(renamed.clone()));
+        let quantifier = Box::new(requantify(quantifier, renamed, body));
+
+        return Some((
+            Transformation::VariableRenaming,
+            if on_left {
+                make(quantifier, Box::new(other.clone()))
+            } else {
+                make(Box::new(other.clone()), quantifier)
+            },
+        ));
+    }
+
+    let other = Box::new(shift(other, 1));
+    let body = Box::new(body);
+    Some((
+        Transformation::QuantifierExtraction,
+        requantify(
+            quantifier,
+            iter,
+            if on_left {
+                make(body, other)
+            } else {
+                make(other, body)
+            },
+        ),
+    ))
+}
+
+fn sentence_letters(expr: &Expr, letters: &mut HashSet<String>) -> EngineResult {
+    match expr {
+        Expr::Literal(literal) => {
+            letters.insert(literal.0.clone());
+        }
+        Expr::Tautology | Expr::Contradiction => {}
+        Expr::Not(inner) => sentence_letters(inner, letters)?,
+        Expr::And(l, r)
+        | Expr::Or(l, r)
+        | Expr::Xor(l, r)
+        | Expr::Conditional(l, r)
+        | Expr::Biconditional(l, r) => {
+            sentence_letters(l, letters)?;
+            sentence_letters(r, letters)?;
+        }
+        expr => Err(ValidationError::NotPropositional(ExprDiscriminants::from(
+            expr,
+        )))?,
+    }
+    Ok(())
+}
+
+/// Finds the first binary sub-statement with no other binary sub-statements inside it
+fn innermost_compound(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Not(inner) => innermost_compound(inner),
+        Expr::And(l, r)
+        | Expr::Or(l, r)
+        | Expr::Xor(l, r)
+        | Expr::Conditional(l, r)
+        | Expr::Biconditional(l, r) => innermost_compound(l)
+            .or_else(|| innermost_compound(r))
+            .or(Some(expr)),
+        _ => None,
+    }
+}
+
+/// Replaces the first occurrence of `from` in `expr` with `to`
+fn replace(expr: &Expr, from: &Expr, to: &Expr) -> Expr {
+    rewrite_once(expr, &|v: &Expr| {
+        (v == from).then(|| (Transformation::Tseitin, to.clone()))
+    })
+    .map_or_else(|| expr.clone(), |(_, v)| v)
+}
+
+/// The clauses saying `letter` is equivalent to the binary statement `expr`, whose sides are atomic
+fn definition(letter: &Expr, expr: &Expr) -> Vec<Expr> {
+    let neg = |v: &Expr| match v {
+        Expr::Not(inner) => inner.as_ref().clone(),
+        v => Expr::Not(Box::new(v.clone())),
+    };
+    let clause = |literals: &[Expr]| {
+        literals
+            .iter()
+            .cloned()
+            .reduce(|a, b| Expr::Or(Box::new(a), Box::new(b)))
+            .unwrap()
+    };
+    let p = letter.clone();
+
+    match expr {
+        Expr::And(a, b) => vec![
+            clause(&[neg(&p), *a.clone()]),
+            clause(&[neg(&p), *b.clone()]),
+            clause(&[p, neg(a), neg(b)]),
+        ],
+        Expr::Or(a, b) => vec![
+            clause(&[neg(&p), *a.clone(), *b.clone()]),
+            clause(&[p.clone(), neg(a)]),
+            clause(&[p, neg(b)]),
+        ],
+        Expr::Conditional(a, b) => vec![
+            clause(&[neg(&p), neg(a), *b.clone()]),
+            clause(&[p.clone(), *a.clone()]),
+            clause(&[p, neg(b)]),
+        ],
+        Expr::Biconditional(a, b) => vec![
+            clause(&[neg(&p), neg(a), *b.clone()]),
+            clause(&[neg(&p), *a.clone(), neg(b)]),
+            clause(&[p.clone(), *a.clone(), *b.clone()]),
+            clause(&[p, neg(a), neg(b)]),
+        ],
+        Expr::Xor(a, b) => vec![
+            clause(&[neg(&p), *a.clone(), *b.clone()]),
+            clause(&[neg(&p), neg(a), neg(b)]),
+            clause(&[p.clone(), neg(a), *b.clone()]),
+            clause(&[p, *a.clone(), neg(b)]),
+        ],
+        _ => unreachable!(),
+    }
+}
+
+fn conjunction(exprs: impl Iterator<Item = Expr>) -> Expr {
+    exprs
+        .reduce(|a, b| Expr::And(Box::new(a), Box::new(b)))
+        .unwrap()
+}
@@ -0,0 +1,383 @@
+use std::collections::HashSet;
+
+use yggdrasil_grammar::expr::{
+    constantexpr::{Constant, ConstantExpr},
+    Expr,
+};
+
+use crate::{
+    completeness::{branch_constants, is_decomposed},
+    rules::{
+        identity::{identity_sides, is_reflexive, replace_identical, IdentityRule},
+        quantifier::QuantifierRule,
+        rule_for, ExprSet, Rule,
+    },
+    substitution::alpha_eq,
+    tree::{Annotation, AppliedRule, BranchId, NodeId, Tree},
+};
+
+/// Limits that stop the prover from running forever on trees that never finish
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProverConfig {
+    /// The most statements a single path can have
+    pub max_depth: usize,
+
+    /// The most quantifier instantiations a single path can have
+    pub max_instantiations: usize,
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 200,
+            max_instantiations: 50,
+        }
+    }
+}
+
+/// How the prover finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofStatus {
+    /// Every branch closed
+    Closed,
+
+    /// This branch is open and has nothing left to decompose
+    Open(BranchId),
+
+    /// Some branch hit a limit in [ProverConfig] before it closed or finished
+    LimitReached,
+}
+
+/// A tree built by the prover
+#[derive(Debug, Clone)]
+pub struct Proof {
+    pub tree: Tree,
+    pub status: ProofStatus,
+}
+
+/// What happened when a branch was expanded as far as it could go without splitting
+enum Step {
+    Closed,
+    Open,
+    Split(BranchId, BranchId),
+    LimitReached,
+}
+
+/// Builds a tree to check whether `premises` entail `conclusion`, starting from the premises and
+/// the negated conclusion
+pub fn prove(premises: &[Expr], conclusion: &Expr, config: &ProverConfig) -> Proof {
+    build_tree(
+        premises
+            .iter()
+            .cloned()
+            .chain([Expr::Not(Box::new(conclusion.clone()))]),
+        config,
+    )
+}
+
+/// Builds a tree from `statements` until every branch closes, an open branch is fully decomposed,
+/// or a limit is reached.
+///
+/// Rules are applied in a fixed order on each branch: stacking rules, then substitution of
+/// identicals into atomic statements, then existential instantiation with a new constant, then
+/// branching rules, and finally universal instantiation with each constant on the branch. Any new
+/// statements are decomposed before the next universal instance is added, and each instance comes
+/// from the universal that was instantiated least recently, using the constant that appeared on
+/// the branch first, so no statement is put off forever.
+pub fn build_tree(statements: impl IntoIterator<Item = Expr>, config: &ProverConfig) -> Proof {
+    let mut tree = Tree::new();
+    let root = tree.root();
+    for statement in statements {
+        tree.add_node(root, statement, None);
+    }
+
+    let mut pending = vec![root];
+    let mut limit_reached = false;
+
+    while let Some(branch) = pending.pop() {
+        match expand(&mut tree, branch, config) {
+            Step::Closed => {}
+            Step::Open => {
+                return Proof {
+                    tree,
+                    status: ProofStatus::Open(branch),
+                }
+            }
+            Step::Split(first, second) => {
+                pending.push(second);
+                pending.push(first);
+            }
+            Step::LimitReached => limit_reached = true,
+        }
+    }
+
+    Proof {
+        tree,
+        status: if limit_reached {
+            ProofStatus::LimitReached
+        } else {
+            ProofStatus::Closed
+        },
+    }
+}
+
+fn expand(tree: &mut Tree, branch: BranchId, config: &ProverConfig) -> Step {
+    loop {
+        if tree.closure(branch).is_some() {
+            return Step::Closed;
+        }
+
+        let path = tree.path(branch);
+        if path.len() >= config.max_depth {
+            return Step::LimitReached;
+        }
+
+        let exprs = path.iter().map(|v| &tree.node(*v).expr).collect::<Vec<_>>();
+        let constants = branch_constants(exprs.iter().copied());
+        let on_path = |expr: &Expr| exprs.iter().any(|v| alpha_eq(v, expr));
+        let undecomposed =
+            |node: &NodeId| !is_decomposed(&tree.node(*node).expr, &exprs, &constants);
+
+        let instantiations = path
+            .iter()
+            .filter(|v| {
+                matches!(
+                    tree.node(**v).rule,
+                    Some(Annotation {
+                        rule: AppliedRule::Quantifier(
+                            QuantifierRule::UniversalInstantiation
+                                | QuantifierRule::ExistentialInstantiation
+                        ),
+                        ..
+                    })
+                )
+            })
+            .count();
+
+        // stacking rules, including the quantifier negation rules
+        let stacking = path.iter().copied().filter(undecomposed).find_map(|node| {
+            let expr = &tree.node(node).expr;
+            match rule_for(expr)? {
+                Rule::Stack(rule) => {
+                    let results = ordered(expr, rule.decompose(expr).ok()?);
+                    Some((node, AppliedRule::Stack(rule), results))
+                }
+                Rule::Quantifier(
+                    rule @ (QuantifierRule::NotUniversal | QuantifierRule::NotExistential),
+                ) => {
+                    let result = rule.decompose(expr, None, []).ok()?;
+                    Some((node, AppliedRule::Quantifier(rule), vec![result]))
+                }
+                _ => None,
+            }
+        });
+
+        if let Some((source, rule, results)) = stacking {
+            let results = results
+                .into_iter()
+                .filter(|v| !on_path(v))
+                .collect::<Vec<_>>();
+            for result in results {
+                tree.add_node(branch, result, Some(Annotation { rule, source }));
+            }
+            continue;
+        }
+
+        // substitution of identicals into statements no other rule decomposes
+        let identities = path
+            .iter()
+            .copied()
+            .filter(|v| identity_sides(&tree.node(*v).expr).is_ok())
+            .collect::<Vec<_>>();
+        let substitution = path
+            .iter()
+            .copied()
+            .filter(|v| rule_for(&tree.node(*v).expr).is_none())
+            .filter(undecomposed)
+            .find_map(|node| {
+                identities.iter().find_map(|identity| {
+                    let (left, right) = identity_sides(&tree.node(*identity).expr).ok()?;
+                    [(left, right), (right, left)]
+                        .into_iter()
+                        .find_map(|(from, to)| {
+                            let result = replace_identical(&tree.node(node).expr, from, to);
+                            (!is_reflexive(&result) && !on_path(&result))
+                                .then_some((node, *identity, result))
+                        })
+                })
+            });
+
+        if let Some((source, identity, result)) = substitution {
+            tree.add_node(
+                branch,
+                result,
+                Some(Annotation {
+                    rule: AppliedRule::Identity {
+                        rule: IdentityRule::SubstitutionOfIdenticals,
+                        identity,
+                    },
+                    source,
+                }),
+            );
+            continue;
+        }
+
+        // existential instantiation, with a constant new to the branch
+        let existential = path.iter().copied().filter(undecomposed).find(|node| {
+            rule_for(&tree.node(*node).expr)
+                == Some(Rule::Quantifier(QuantifierRule::ExistentialInstantiation))
+        });
+
+        if let Some(source) = existential {
+            if instantiations >= config.max_instantiations {
+                return Step::LimitReached;
+            }
+
+            let rule = QuantifierRule::ExistentialInstantiation;
+            let term = ConstantExpr::Constant(fresh_constant(&constants));
+            let result = rule
+                .decompose(&tree.node(source).expr, Some(&term), exprs)
+                .unwrap();
+            tree.add_node(
+                branch,
+                result,
+                Some(Annotation {
+                    rule: AppliedRule::Quantifier(rule),
+                    source,
+                }),
+            );
+            continue;
+        }
+
+        // branching rules
+        let branching = path.iter().copied().filter(undecomposed).find_map(|node| {
+            let expr = &tree.node(node).expr;
+            match rule_for(expr)? {
+                Rule::Branch(rule) => {
+                    let (first, second) = rule.decompose(expr).ok()?;
+                    Some((node, rule, ordered(expr, first), ordered(expr, second)))
+                }
+                _ => None,
+            }
+        });
+
+        if let Some((source, rule, first, second)) = branching {
+            let children = tree.split(branch, rule, source).unwrap();
+            for (child, statements) in [(children.0, first), (children.1, second)] {
+                for statement in statements {
+                    tree.add_node(child, statement, None);
+                }
+            }
+            return Step::Split(children.0, children.1);
+        }
+
+        // universal instantiation, with every constant on the branch in the order they appeared,
+        // or a new one if there are none
+        let terms = if constants.is_empty() {
+            vec![fresh_constant(&constants)]
+        } else {
+            let mut terms = Vec::<Constant>::new();
+            for expr in &exprs {
+                let mut new = branch_constants([*expr])
+                    .into_iter()
+                    .filter(|v| !terms.contains(v))
+                    .collect::<Vec<_>>();
+                new.sort_by(|a, b| a.0.cmp(&b.0));
+                terms.extend(new);
+            }
+            terms
+        };
+
+        let rule = QuantifierRule::UniversalInstantiation;
+        let last_instantiated = |universal: NodeId| {
+            path.iter().rposition(|v| {
+                matches!(
+                    tree.node(*v).rule,
+                    Some(Annotation {
+                        rule: AppliedRule::Quantifier(QuantifierRule::UniversalInstantiation),
+                        source,
+                    }) if source == universal
+                )
+            })
+        };
+        let universal = path
+            .iter()
+            .copied()
+            .filter(|node| rule_for(&tree.node(*node).expr) == Some(Rule::Quantifier(rule)))
+            .filter_map(|node| {
+                terms.iter().find_map(|term| {
+                    let term = ConstantExpr::Constant(term.clone());
+                    let result = rule
+                        .decompose(&tree.node(node).expr, Some(&term), [])
+                        .ok()?;
+                    (!on_path(&result)).then_some((node, result))
+                })
+            })
+            // universals that were never instantiated come first
+            .min_by_key(|(node, _)| last_instantiated(*node));
+
+        if let Some((source, result)) = universal {
+            if instantiations >= config.max_instantiations {
+                return Step::LimitReached;
+            }
+
+            tree.add_node(
+                branch,
+                result,
+                Some(Annotation {
+                    rule: AppliedRule::Quantifier(rule),
+                    source,
+                }),
+            );
+            continue;
+        }
+
+        return Step::Open;
+    }
+}
+
+/// Puts the statements a rule produced in the same order as the parts of `source` they came from
+fn ordered(source: &Expr, set: ExprSet) -> Vec<Expr> {
+    let source = match source {
+        Expr::Not(inner) => inner.as_ref(),
+        v => v,
+    };
+    let parts = match source {
+        Expr::And(left, right)
+        | Expr::Or(left, right)
+        | Expr::Xor(left, right)
+        | Expr::Conditional(left, right)
+        | Expr::Biconditional(left, right) => vec![left.as_ref(), right.as_ref()],
+        _ => vec![],
+    };
+
+    let position = |expr: &Expr| {
+        let expr = match expr {
+            Expr::Not(inner) if !parts.iter().any(|v| alpha_eq(v, expr)) => inner.as_ref(),
+            v => v,
+        };
+        parts.iter().position(|v| alpha_eq(v, expr))
+    };
+
+    let mut set = set.into_iter().map(|v| *v).collect::<Vec<_>>();
+    set.sort_by_key(|v| position(v));
+    set
+}
+
+/// The first constant out of `a`, `b`, ..., `s`, `a1`, `b1`, ... that is not in `used`
+///
+/// `t` to `z` are variables, so they are never used as constants
+fn fresh_constant(used: &HashSet<Constant>) -> Constant {
+    (0..)
+        .flat_map(|i| {
+            ('a'..='s').map(move |c| {
+                Constant(if i == 0 {
+                    c.to_string()
+                } else {
+                    format!("{}{}", c, i)
+                })
+            })
+        })
+        .find(|v| !used.contains(v))
+        .unwrap()
+}
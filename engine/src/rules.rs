@@ -1 +1,49 @@
+use std::collections::HashSet;
+
+use branch::BranchRule;
+use quantifier::QuantifierRule;
+use stack::StackRule;
+use yggdrasil_grammar::expr::Expr;
+
 pub mod branch;
+pub mod identity;
+pub mod quantifier;
+pub mod stack;
+
+/// A set of statements produced by decomposing a statement
+pub type ExprSet = HashSet<Box<Expr>>;
+
+/// Any rule that decomposes a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    Stack(StackRule),
+    Branch(BranchRule),
+    Quantifier(QuantifierRule),
+}
+
+/// Finds the rule that decomposes a statement, or [None] if it can't be decomposed any further
+pub fn rule_for(expr: &Expr) -> Option<Rule> {
+    match expr {
+        Expr::And(..) => Some(Rule::Stack(StackRule::And)),
+        Expr::Or(..) => Some(Rule::Branch(BranchRule::Or)),
+        Expr::Xor(..) => Some(Rule::Branch(BranchRule::Xor)),
+        Expr::Conditional(..) => Some(Rule::Branch(BranchRule::Conditional)),
+        Expr::Biconditional(..) => Some(Rule::Branch(BranchRule::Biconditional)),
+        Expr::Universal { .. } => Some(Rule::Quantifier(QuantifierRule::UniversalInstantiation)),
+        Expr::Existential { .. } => {
+            Some(Rule::Quantifier(QuantifierRule::ExistentialInstantiation))
+        }
+        Expr::Not(inner) => match inner.as_ref() {
+            Expr::Not(_) => Some(Rule::Stack(StackRule::DoubleNegation)),
+            Expr::Or(..) => Some(Rule::Stack(StackRule::Nor)),
+            Expr::Conditional(..) => Some(Rule::Stack(StackRule::NotConditional)),
+            Expr::And(..) => Some(Rule::Branch(BranchRule::Nand)),
+            Expr::Xor(..) => Some(Rule::Branch(BranchRule::NotXor)),
+            Expr::Biconditional(..) => Some(Rule::Branch(BranchRule::NotBiconditional)),
+            Expr::Universal { .. } => Some(Rule::Quantifier(QuantifierRule::NotUniversal)),
+            Expr::Existential { .. } => Some(Rule::Quantifier(QuantifierRule::NotExistential)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -1,10 +1,11 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, ops::Deref};
 
-use crate::{error::ValidationError, EngineResult};
+use super::ExprSet;
+use crate::{error::ValidationError, util::expr_maybe_list_starts_with, EngineResult};
 use strum::{Display, EnumIter, EnumMessage, EnumString};
 use yggdrasil_grammar::expr::{Expr, ExprDiscriminants};
 
-#[derive(Debug, Clone, Copy, EnumIter, EnumMessage, Display, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumMessage, Display, EnumString)]
 pub enum BranchRule {
     /// p | q
     Or,
@@ -20,9 +21,13 @@ pub enum BranchRule {
 
     /// ~(p <-> q)
     NotBiconditional,
-}
 
-type ExprSet = HashSet<Box<Expr>>;
+    /// p ⊕ q
+    Xor,
+
+    /// ~(p ⊕ q)
+    NotXor,
+}
 
 impl BranchRule {
     /// Decomposes an [Expr] into two branches
@@ -110,6 +115,71 @@ impl BranchRule {
                     ))?
                 }
             }
+            Self::Xor => {
+                if let Expr::Xor(left, right) = expr {
+                    Ok((
+                        HashSet::from([left.clone(), Box::new(Expr::Not(right.clone()))]),
+                        HashSet::from([Box::new(Expr::Not(left.clone())), right.clone()]),
+                    ))
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Xor,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+            Self::NotXor => {
+                if let Expr::Not(expr) = expr {
+                    if let Expr::Xor(left, right) = expr.as_ref() {
+                        Ok((
+                            HashSet::from([left.clone(), right.clone()]),
+                            HashSet::from([
+                                Box::new(Expr::Not(left.clone())),
+                                Box::new(Expr::Not(right.clone())),
+                            ]),
+                        ))
+                    } else {
+                        Err(ValidationError::InvalidStatementType(
+                            ExprDiscriminants::Xor,
+                            ExprDiscriminants::from(expr.as_ref()),
+                        ))?
+                    }
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Not,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
         }
     }
 }
+
+/// Which sub-branches of a split don't start with the statements a [BranchRule] produces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubBranchError {
+    First,
+    Second,
+    Both,
+}
+
+/// Checks that each sub-branch starts with one of the two sets of statements from
+/// [BranchRule::decompose], in either order
+pub fn check_sub_branches<T: Deref<Target = Option<Expr>>>(
+    (correct1, correct2): &(ExprSet, ExprSet),
+    first: impl Iterator<Item = T> + Clone,
+    second: impl Iterator<Item = T> + Clone,
+) -> Option<SubBranchError> {
+    let one = expr_maybe_list_starts_with(first.clone(), correct1);
+    let two = expr_maybe_list_starts_with(first, correct2);
+
+    let three = expr_maybe_list_starts_with(second.clone(), correct1);
+    let four = expr_maybe_list_starts_with(second, correct2);
+
+    match (one, two, three, four) {
+        (true, _, _, true) | (_, true, true, _) => None,
+        (true, _, _, _) | (_, true, _, _) => Some(SubBranchError::Second),
+        (_, _, true, _) | (_, _, _, true) => Some(SubBranchError::First),
+        _ => Some(SubBranchError::Both),
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use super::ExprSet;
+use crate::{error::ValidationError, substitution::alpha_eq_term, EngineResult};
+use strum::{Display, EnumIter, EnumMessage, EnumString};
+use yggdrasil_grammar::expr::{constantexpr::ConstantExpr, Expr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumMessage, Display, EnumString)]
+pub enum IdentityRule {
+    /// a = b, P(a) => P(b)
+    SubstitutionOfIdenticals,
+}
+
+impl IdentityRule {
+    /// Decomposes an [Expr] using the identity statement `identity`, into the statements made by
+    /// replacing every occurrence of one side of the identity with the other.
+    /// Statements that would be the same as `expr` are left out.
+    pub fn decompose(&self, identity: &Expr, expr: &Expr) -> EngineResult<ExprSet> {
+        match self {
+            Self::SubstitutionOfIdenticals => {
+                let (left, right) = identity_sides(identity)?;
+
+                Ok([(left, right), (right, left)]
+                    .into_iter()
+                    .map(|(from, to)| replace_identical(expr, from, to))
+                    .filter(|v| v != expr)
+                    .map(Box::new)
+                    .collect::<HashSet<_>>())
+            }
+        }
+    }
+
+    /// Checks that `result` is a correct application of this rule to `expr`: some of the
+    /// occurrences of one side of `identity` have been replaced with the other side, including
+    /// occurrences inside of function terms.
+    pub fn check(&self, identity: &Expr, expr: &Expr, result: &Expr) -> EngineResult {
+        match self {
+            Self::SubstitutionOfIdenticals => {
+                let (left, right) = identity_sides(identity)?;
+
+                if [(left, right), (right, left)]
+                    .into_iter()
+                    .any(|(from, to)| replaced(expr, result, from, to))
+                {
+                    Ok(())
+                } else {
+                    Err(ValidationError::InvalidDecomposition(self.to_string()))?
+                }
+            }
+        }
+    }
+}
+
+/// Finds the two sides of an identity statement like `a = b`
+pub fn identity_sides(expr: &Expr) -> EngineResult<(&ConstantExpr, &ConstantExpr)> {
+    match expr {
+        Expr::Equals(left, right) => Ok((left, right)),
+        _ => Err(ValidationError::ExpectedIdentity)?,
+    }
+}
+
+/// Checks whether a statement is an identity between a term and itself, like `a = a`
+pub fn is_reflexive(expr: &Expr) -> bool {
+    identity_sides(expr).is_ok_and(|(left, right)| alpha_eq_term(left, right))
+}
+
+/// Replaces every occurrence of the term `from` in `expr` with `to`
+pub fn replace_identical(expr: &Expr, from: &ConstantExpr, to: &ConstantExpr) -> Expr {
+    fn replace_term(term: &ConstantExpr, from: &ConstantExpr, to: &ConstantExpr) -> ConstantExpr {
+        match term {
+            _ if alpha_eq_term(term, from) => to.clone(),
+            ConstantExpr::Function { func, args } => ConstantExpr::Function {
+                func: func.clone(),
+                args: args.iter().map(|v| replace_term(v, from, to)).collect(),
+            },
+            ConstantExpr::Operator(op, left, right) => ConstantExpr::Operator(
+                op.clone(),
+                Box::new(replace_term(left, from, to)),
+                Box::new(replace_term(right, from, to)),
+            ),
+            v => v.clone(),
+        }
+    }
+
+    let rec = |v: &Expr| Box::new(replace_identical(v, from, to));
+
+    match expr {
+        Expr::Predicate { pred, args } => Expr::Predicate {
+            pred: pred.clone(),
+            args: args.iter().map(|v| replace_term(v, from, to)).collect(),
+        },
+        Expr::ConstantValue(v) => Expr::ConstantValue(replace_term(v, from, to)),
+        Expr::Equals(l, r) => Expr::Equals(replace_term(l, from, to), replace_term(r, from, to)),
+        Expr::Not(v) => Expr::Not(rec(v)),
+        Expr::And(l, r) => Expr::And(rec(l), rec(r)),
+        Expr::Or(l, r) => Expr::Or(rec(l), rec(r)),
+        Expr::Xor(l, r) => Expr::Xor(rec(l), rec(r)),
+        Expr::Conditional(l, r) => Expr::Conditional(rec(l), rec(r)),
+        Expr::Biconditional(l, r) => Expr::Biconditional(rec(l), rec(r)),
+        Expr::Universal { iter, expr } => Expr::Universal {
+            iter: iter.clone(),
+            expr: rec(expr),
+        },
+        Expr::Existential { iter, expr } => Expr::Existential {
+            iter: iter.clone(),
+            expr: rec(expr),
+        },
+        v => v.clone(),
+    }
+}
+
+/// Checks whether `result` is `expr` with any number of occurrences of the term `from` replaced
+/// with `to`
+fn replaced(expr: &Expr, result: &Expr, from: &ConstantExpr, to: &ConstantExpr) -> bool {
+    fn replaced_term(
+        term: &ConstantExpr,
+        result: &ConstantExpr,
+        from: &ConstantExpr,
+        to: &ConstantExpr,
+    ) -> bool {
+        if alpha_eq_term(term, result) || (alpha_eq_term(term, from) && alpha_eq_term(result, to)) {
+            return true;
+        }
+
+        match (term, result) {
+            (
+                ConstantExpr::Function { func, args },
+                ConstantExpr::Function {
+                    func: func_b,
+                    args: args_b,
+                },
+            ) => {
+                func == func_b
+                    && args.len() == args_b.len()
+                    && args
+                        .iter()
+                        .zip(args_b)
+                        .all(|(a, b)| replaced_term(a, b, from, to))
+            }
+            (ConstantExpr::Operator(op, l1, r1), ConstantExpr::Operator(op_b, l2, r2)) => {
+                op == op_b && replaced_term(l1, l2, from, to) && replaced_term(r1, r2, from, to)
+            }
+            _ => false,
+        }
+    }
+
+    let rec = |a: &Expr, b: &Expr| replaced(a, b, from, to);
+
+    match (expr, result) {
+        (
+            Expr::Predicate { pred, args },
+            Expr::Predicate {
+                pred: pred_b,
+                args: args_b,
+            },
+        ) => {
+            pred == pred_b
+                && args.len() == args_b.len()
+                && args
+                    .iter()
+                    .zip(args_b)
+                    .all(|(a, b)| replaced_term(a, b, from, to))
+        }
+        (Expr::ConstantValue(a), Expr::ConstantValue(b)) => replaced_term(a, b, from, to),
+        (Expr::Equals(l1, r1), Expr::Equals(l2, r2)) => {
+            replaced_term(l1, l2, from, to) && replaced_term(r1, r2, from, to)
+        }
+        (Expr::Not(a), Expr::Not(b)) => rec(a, b),
+        (Expr::And(l1, r1), Expr::And(l2, r2))
+        | (Expr::Or(l1, r1), Expr::Or(l2, r2))
+        | (Expr::Xor(l1, r1), Expr::Xor(l2, r2))
+        | (Expr::Conditional(l1, r1), Expr::Conditional(l2, r2))
+        | (Expr::Biconditional(l1, r1), Expr::Biconditional(l2, r2)) => rec(l1, l2) && rec(r1, r2),
+        (
+            Expr::Universal { iter, expr },
+            Expr::Universal {
+                iter: iter_b,
+                expr: expr_b,
+            },
+        )
+        | (
+            Expr::Existential { iter, expr },
+            Expr::Existential {
+                iter: iter_b,
+                expr: expr_b,
+            },
+        ) => iter.name == iter_b.name && rec(expr, expr_b),
+        (a, b) => a == b,
+    }
+}
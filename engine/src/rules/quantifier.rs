@@ -0,0 +1,212 @@
+use crate::{
+    error::ValidationError,
+    substitution::{alpha_eq, substitute},
+    util::expr_constants,
+    EngineResult,
+};
+use strum::{Display, EnumIter, EnumMessage, EnumString};
+use yggdrasil_grammar::expr::{
+    constantexpr::{ConstantExpr, ConstantExprDiscriminants},
+    variable::Variable,
+    Expr, ExprDiscriminants,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumMessage, Display, EnumString)]
+pub enum QuantifierRule {
+    /// @x P(x) => P(a)
+    UniversalInstantiation,
+
+    /// /x P(x) => P(a), where a is new to the branch
+    ExistentialInstantiation,
+
+    /// ~@x P(x) => /x ~P(x)
+    NotUniversal,
+
+    /// ~/x P(x) => @x ~P(x)
+    NotExistential,
+}
+
+impl QuantifierRule {
+    /// Decomposes an [Expr] into the single statement that should be added to the current branch.
+    ///
+    /// The instantiation rules need a `term` to replace the quantified variable with, and
+    /// existential instantiation checks that it is new to the statements in `branch`.
+    pub fn decompose<'a>(
+        &self,
+        expr: &Expr,
+        term: Option<&ConstantExpr>,
+        branch: impl IntoIterator<Item = &'a Expr>,
+    ) -> EngineResult<Expr> {
+        match self {
+            Self::UniversalInstantiation => {
+                if let Expr::Universal { iter, expr } = expr {
+                    let term = term.ok_or(ValidationError::MissingTerm)?;
+                    Ok(substitute(expr, iter, term))
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Universal,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+            Self::ExistentialInstantiation => {
+                if let Expr::Existential { iter, expr } = expr {
+                    let term = term.ok_or(ValidationError::MissingTerm)?;
+                    let ConstantExpr::Constant(constant) = term else {
+                        return Err(ValidationError::ExpectedConstant(
+                            ConstantExprDiscriminants::from(term),
+                        ))?;
+                    };
+
+                    if branch
+                        .into_iter()
+                        .any(|v| expr_constants(v).contains(constant))
+                    {
+                        Err(ValidationError::ConstantNotNew(constant.0.clone()))?
+                    }
+
+                    Ok(substitute(expr, iter, term))
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Existential,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+            Self::NotUniversal => {
+                if let Expr::Not(expr) = expr {
+                    if let Expr::Universal { iter, expr } = expr.as_ref() {
+                        Ok(Expr::Existential {
+                            iter: iter.clone(),
+                            expr: Box::new(Expr::Not(expr.clone())),
+                        })
+                    } else {
+                        Err(ValidationError::InvalidStatementType(
+                            ExprDiscriminants::Universal,
+                            ExprDiscriminants::from(expr.as_ref()),
+                        ))?
+                    }
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Not,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+            Self::NotExistential => {
+                if let Expr::Not(expr) = expr {
+                    if let Expr::Existential { iter, expr } = expr.as_ref() {
+                        Ok(Expr::Universal {
+                            iter: iter.clone(),
+                            expr: Box::new(Expr::Not(expr.clone())),
+                        })
+                    } else {
+                        Err(ValidationError::InvalidStatementType(
+                            ExprDiscriminants::Existential,
+                            ExprDiscriminants::from(expr.as_ref()),
+                        ))?
+                    }
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Not,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+        }
+    }
+
+    /// Checks that `result` is a correct application of this rule to `expr`, up to renaming of
+    /// quantified variables.
+    ///
+    /// For the instantiation rules the term is read off `result`, so the user is free to pick it.
+    pub fn check<'a>(
+        &self,
+        expr: &Expr,
+        result: &Expr,
+        branch: impl IntoIterator<Item = &'a Expr>,
+    ) -> EngineResult {
+        let expected = match (self, expr) {
+            (Self::UniversalInstantiation, Expr::Universal { iter, expr: inner })
+            | (Self::ExistentialInstantiation, Expr::Existential { iter, expr: inner }) => {
+                match find_instance_term(inner, iter, result) {
+                    Some(term) => self.decompose(expr, Some(&term), branch)?,
+                    // the variable is never used, so the instance is just the inner statement
+                    None => inner.as_ref().clone(),
+                }
+            }
+            _ => self.decompose(expr, None, branch)?,
+        };
+
+        if alpha_eq(&expected, result) {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidInstance)?
+        }
+    }
+}
+
+/// Walks `pattern` and `target` together, returning the term in `target` at the first place
+/// `var` is used freely in `pattern`
+fn find_instance_term(pattern: &Expr, var: &Variable, target: &Expr) -> Option<ConstantExpr> {
+    fn find_term(
+        pattern: &ConstantExpr,
+        var: &Variable,
+        target: &ConstantExpr,
+    ) -> Option<ConstantExpr> {
+        match (pattern, target) {
+            (ConstantExpr::Variable(v), _) if v.name == var.name => Some(target.clone()),
+            (
+                ConstantExpr::Function { args, .. },
+                ConstantExpr::Function {
+                    args: target_args, ..
+                },
+            ) => args
+                .iter()
+                .zip(target_args)
+                .find_map(|(a, b)| find_term(a, var, b)),
+            (
+                ConstantExpr::Operator(_, left, right),
+                ConstantExpr::Operator(_, target_left, target_right),
+            ) => find_term(left, var, target_left).or_else(|| find_term(right, var, target_right)),
+            _ => None,
+        }
+    }
+
+    match (pattern, target) {
+        (
+            Expr::Predicate { args, .. },
+            Expr::Predicate {
+                args: target_args, ..
+            },
+        ) => args
+            .iter()
+            .zip(target_args)
+            .find_map(|(a, b)| find_term(a, var, b)),
+        (Expr::ConstantValue(a), Expr::ConstantValue(b)) => find_term(a, var, b),
+        (Expr::Equals(l1, r1), Expr::Equals(l2, r2)) => {
+            find_term(l1, var, l2).or_else(|| find_term(r1, var, r2))
+        }
+        (Expr::Not(a), Expr::Not(b)) => find_instance_term(a, var, b),
+        (Expr::And(l1, r1), Expr::And(l2, r2))
+        | (Expr::Or(l1, r1), Expr::Or(l2, r2))
+        | (Expr::Xor(l1, r1), Expr::Xor(l2, r2))
+        | (Expr::Conditional(l1, r1), Expr::Conditional(l2, r2))
+        | (Expr::Biconditional(l1, r1), Expr::Biconditional(l2, r2)) => {
+            find_instance_term(l1, var, l2).or_else(|| find_instance_term(r1, var, r2))
+        }
+        (
+            Expr::Universal { iter, expr },
+            Expr::Universal {
+                expr: target_expr, ..
+            },
+        )
+        | (
+            Expr::Existential { iter, expr },
+            Expr::Existential {
+                expr: target_expr, ..
+            },
+        ) if iter.name != var.name => find_instance_term(expr, var, target_expr),
+        _ => None,
+    }
+}
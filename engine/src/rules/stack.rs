@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use super::ExprSet;
+use crate::{error::ValidationError, EngineResult};
+use strum::{Display, EnumIter, EnumMessage, EnumString};
+use yggdrasil_grammar::expr::{Expr, ExprDiscriminants};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumMessage, Display, EnumString)]
+pub enum StackRule {
+    /// p & q
+    And,
+
+    /// ~(p | q)
+    Nor,
+
+    /// ~(p -> q)
+    NotConditional,
+
+    /// ~~p
+    DoubleNegation,
+}
+
+impl StackRule {
+    /// Decomposes an [Expr] into the statements that should be added to the current branch
+    pub fn decompose(&self, expr: &Expr) -> EngineResult<ExprSet> {
+        match self {
+            Self::And => {
+                if let Expr::And(left, right) = expr {
+                    Ok(HashSet::from([left.clone(), right.clone()]))
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::And,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+            Self::Nor => {
+                if let Expr::Not(expr) = expr {
+                    if let Expr::Or(left, right) = expr.as_ref() {
+                        Ok(HashSet::from([
+                            Box::new(Expr::Not(left.clone())),
+                            Box::new(Expr::Not(right.clone())),
+                        ]))
+                    } else {
+                        Err(ValidationError::InvalidStatementType(
+                            ExprDiscriminants::Or,
+                            ExprDiscriminants::from(expr.as_ref()),
+                        ))?
+                    }
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Not,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+            Self::NotConditional => {
+                if let Expr::Not(expr) = expr {
+                    if let Expr::Conditional(left, right) = expr.as_ref() {
+                        Ok(HashSet::from([
+                            left.clone(),
+                            Box::new(Expr::Not(right.clone())),
+                        ]))
+                    } else {
+                        Err(ValidationError::InvalidStatementType(
+                            ExprDiscriminants::Conditional,
+                            ExprDiscriminants::from(expr.as_ref()),
+                        ))?
+                    }
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Not,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+            Self::DoubleNegation => {
+                if let Expr::Not(expr) = expr {
+                    if let Expr::Not(inner) = expr.as_ref() {
+                        Ok(HashSet::from([inner.clone()]))
+                    } else {
+                        Err(ValidationError::InvalidStatementType(
+                            ExprDiscriminants::Not,
+                            ExprDiscriminants::from(expr.as_ref()),
+                        ))?
+                    }
+                } else {
+                    Err(ValidationError::InvalidStatementType(
+                        ExprDiscriminants::Not,
+                        ExprDiscriminants::from(expr),
+                    ))?
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+
+use yggdrasil_grammar::expr::{Expr, ExprDiscriminants};
+
+use crate::{error::ValidationError, EngineResult};
+
+/// The truth value of each sentence letter
+pub type Valuation = BTreeMap<String, bool>;
+
+/// The answer to a question about propositional statements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub holds: bool,
+
+    /// A valuation that shows why, if there is one. See each function for what it shows.
+    pub witness: Option<Valuation>,
+}
+
+/// Checks whether some valuation makes `expr` true, and finds one if so
+pub fn is_satisfiable(expr: &Expr) -> EngineResult<Answer> {
+    let mut problem = Problem::default();
+    problem.require(expr, true)?;
+
+    let witness = problem.solve();
+    Ok(Answer {
+        holds: witness.is_some(),
+        witness,
+    })
+}
+
+/// Checks whether every valuation makes `expr` true, and finds one that doesn't if not
+pub fn is_valid(expr: &Expr) -> EngineResult<Answer> {
+    let mut problem = Problem::default();
+    problem.require(expr, false)?;
+
+    let witness = problem.solve();
+    Ok(Answer {
+        holds: witness.is_none(),
+        witness,
+    })
+}
+
+/// Checks whether every valuation that makes all of `premises` true also makes `conclusion` true,
+/// and finds one that doesn't if not
+pub fn entails(premises: &[Expr], conclusion: &Expr) -> EngineResult<Answer> {
+    let mut problem = Problem::default();
+    for premise in premises {
+        problem.require(premise, true)?;
+    }
+    problem.require(conclusion, false)?;
+
+    let witness = problem.solve();
+    Ok(Answer {
+        holds: witness.is_none(),
+        witness,
+    })
+}
+
+/// Checks whether `a` and `b` are true under exactly the same valuations, and finds one where they
+/// differ if not
+pub fn equivalent(a: &Expr, b: &Expr) -> EngineResult<Answer> {
+    is_valid(&Expr::Biconditional(
+        Box::new(a.clone()),
+        Box::new(b.clone()),
+    ))
+}
+
+/// A set of clauses to satisfy, where each literal is a variable number that is negative when the
+/// variable is negated. Each sentence letter gets a variable, and so does each compound
+/// subformula, with clauses that tie it to the subformulas it was made from (a Tseitin encoding).
+#[derive(Debug, Default)]
+struct Problem {
+    atoms: Vec<(String, isize)>,
+    variables: usize,
+    clauses: Vec<Vec<isize>>,
+}
+
+impl Problem {
+    fn variable(&mut self) -> isize {
+        self.variables += 1;
+        self.variables as isize
+    }
+
+    /// Adds clauses so that `expr` must have the given value
+    fn require(&mut self, expr: &Expr, value: bool) -> EngineResult {
+        let literal = self.encode(expr)?;
+        self.clauses
+            .push(vec![if value { literal } else { -literal }]);
+        Ok(())
+    }
+
+    /// Finds a literal that is true exactly when `expr` is
+    fn encode(&mut self, expr: &Expr) -> EngineResult<isize> {
+        let binary = |this: &mut Self, left: &Expr, right: &Expr| -> EngineResult<_> {
+            Ok((this.encode(left)?, this.encode(right)?, this.variable()))
+        };
+
+        Ok(match expr {
+            Expr::Literal(literal) => match self.atoms.iter().find(|(v, _)| *v == literal.0) {
+                Some((_, v)) => *v,
+                None => {
+                    let v = self.variable();
+                    self.atoms.push((literal.0.clone(), v));
+                    v
+                }
+            },
+            Expr::Tautology | Expr::Contradiction => {
+                let v = self.variable();
+                self.clauses
+                    .push(vec![if *expr == Expr::Tautology { v } else { -v }]);
+                v
+            }
+            Expr::Not(inner) => -self.encode(inner)?,
+            Expr::And(left, right) => {
+                let (a, b, v) = binary(self, left, right)?;
+                self.clauses
+                    .extend([vec![-v, a], vec![-v, b], vec![v, -a, -b]]);
+                v
+            }
+            Expr::Or(left, right) => {
+                let (a, b, v) = binary(self, left, right)?;
+                self.clauses
+                    .extend([vec![-v, a, b], vec![v, -a], vec![v, -b]]);
+                v
+            }
+            Expr::Conditional(left, right) => {
+                let (a, b, v) = binary(self, left, right)?;
+                self.clauses
+                    .extend([vec![-v, -a, b], vec![v, a], vec![v, -b]]);
+                v
+            }
+            Expr::Biconditional(left, right) | Expr::Xor(left, right) => {
+                let (a, b, v) = binary(self, left, right)?;
+                self.clauses.extend([
+                    vec![-v, -a, b],
+                    vec![-v, a, -b],
+                    vec![v, a, b],
+                    vec![v, -a, -b],
+                ]);
+                if matches!(expr, Expr::Xor(..)) {
+                    -v
+                } else {
+                    v
+                }
+            }
+            expr => Err(ValidationError::NotPropositional(ExprDiscriminants::from(
+                expr,
+            )))?,
+        })
+    }
+
+    /// Finds a valuation of the sentence letters that satisfies every clause, if there is one
+    fn solve(&self) -> Option<Valuation> {
+        let mut assignment = vec![None; self.variables + 1];
+        dpll(&self.clauses, &mut assignment).then(|| {
+            self.atoms
+                .iter()
+                .map(|(name, v)| (name.clone(), assignment[*v as usize].unwrap_or(false)))
+                .collect()
+        })
+    }
+}
+
+fn value(assignment: &[Option<bool>], literal: isize) -> Option<bool> {
+    assignment[literal.unsigned_abs()].map(|v| v == (literal > 0))
+}
+
+/// Searches for an assignment that satisfies every clause, setting variables that are forced by
+/// unit propagation before guessing any. On failure, `assignment` is left as it was.
+fn dpll(clauses: &[Vec<isize>], assignment: &mut [Option<bool>]) -> bool {
+    let mut forced = Vec::new();
+    let undo = |assignment: &mut [Option<bool>], forced: &[usize]| {
+        for v in forced {
+            assignment[*v] = None;
+        }
+    };
+
+    loop {
+        let mut changed = false;
+        for clause in clauses {
+            if clause.iter().any(|v| value(assignment, *v) == Some(true)) {
+                continue;
+            }
+
+            let mut unassigned = clause.iter().filter(|v| value(assignment, **v).is_none());
+            match (unassigned.next(), unassigned.next()) {
+                (None, _) => {
+                    undo(assignment, &forced);
+                    return false;
+                }
+                (Some(literal), None) => {
+                    assignment[literal.unsigned_abs()] = Some(*literal > 0);
+                    forced.push(literal.unsigned_abs());
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let guess = clauses
+        .iter()
+        .flatten()
+        .find(|v| value(assignment, **v).is_none())
+        .map(|v| v.unsigned_abs());
+
+    let Some(guess) = guess else {
+        return true;
+    };
+
+    for v in [true, false] {
+        assignment[guess] = Some(v);
+        if dpll(clauses, assignment) {
+            return true;
+        }
+    }
+    assignment[guess] = None;
+
+    undo(assignment, &forced);
+    false
+}
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use yggdrasil_grammar::expr::{
+    constantexpr::{Constant, ConstantExpr},
+    variable::Variable,
+    Expr,
+};
+
+use crate::{
+    herbrand::symbol_names,
+    normal_form::nnf,
+    substitution::{fresh_name, shift, substitute},
+};
+
+/// Removes every existential quantifier from a statement, replacing the variable it binds with a
+/// new function of the variables of the universal quantifiers around it, or a new constant if
+/// there are none. `@x /y F(x, y)` becomes `@x F(x, sk1(x))`.
+///
+/// The result is satisfiable exactly when `expr` is, though it isn't equivalent to it. The
+/// statement is put in [negation normal form](nnf) first so that every existential is in a
+/// positive position. The new symbols are named `sk1`, `sk2`, and so on, skipping any names
+/// already used in `expr`.
+pub fn skolemize(expr: &Expr) -> Expr {
+    let expr = nnf(expr).result().clone();
+
+    let mut used = symbol_names([&expr]);
+    skolemize_in(&expr, &mut Vec::new(), &mut used)
+}
+
+/// `universals` holds the variables of the universal quantifiers around `expr`, innermost last
+fn skolemize_in(expr: &Expr, universals: &mut Vec<Variable>, used: &mut HashSet<String>) -> Expr {
+    let mut rec = |v: &Expr| Box::new(skolemize_in(v, universals, used));
+
+    match expr {
+        Expr::Not(v) => Expr::Not(rec(v)),
+        Expr::And(l, r) => Expr::And(rec(l), rec(r)),
+        Expr::Or(l, r) => Expr::Or(rec(l), rec(r)),
+        Expr::Universal { iter, expr } => {
+            universals.push(iter.clone());
+            let expr = skolemize_in(expr, universals, used);
+            universals.pop();
+
+            Expr::Universal {
+                iter: iter.clone(),
+                expr: Box::new(expr),
+            }
+        }
+        Expr::Existential { iter, expr } => {
+            let name = fresh_name("sk", used);
+            used.insert(name.clone());
+
+            // the variables are used inside the existential, which is one more quantifier away
+            // from each universal than the existential itself
+            let count = universals.len();
+            let args = universals
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    ConstantExpr::Variable(Variable {
+                        name: v.name.clone(),
+                        index: Some(count - i),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let term = if args.is_empty() {
+                ConstantExpr::Constant(Constant(name))
+            } else {
+                ConstantExpr::Function {
+                    func: Constant(name),
+                    args,
+                }
+            };
+
+            // the existential is gone, so everything bound outside of it is one quantifier closer
+            let expr = shift(&substitute(expr, iter, &term), -1);
+            skolemize_in(&expr, universals, used)
+        }
+        v => v.clone(),
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::HashSet;
+
+use yggdrasil_grammar::expr::{constantexpr::ConstantExpr, variable::Variable, Expr};
+
+/// Replaces every free occurrence of `var` in `expr` with `term`.
+///
+/// Occurrences bound by a nested quantifier over a variable with the same name are left alone,
+/// and nested quantifiers are renamed if they would capture a variable used in `term`.
+/// The indices of variables in `term` are shifted so they keep referring to the same quantifiers.
+pub fn substitute(expr: &Expr, var: &Variable, term: &ConstantExpr) -> Expr {
+    substitute_at(expr, var, term, 0)
+}
+
+/// Replaces every occurrence of `var` in `expr` with `term`
+pub fn substitute_term(expr: &ConstantExpr, var: &Variable, term: &ConstantExpr) -> ConstantExpr {
+    substitute_term_at(expr, var, term, 0)
+}
+
+fn substitute_at(expr: &Expr, var: &Variable, term: &ConstantExpr, depth: usize) -> Expr {
+    let rec = |v: &Expr| Box::new(substitute_at(v, var, term, depth));
+
+    match expr {
+        Expr::Predicate { pred, args } => Expr::Predicate {
+            pred: pred.clone(),
+            args: args
+                .iter()
+                .map(|v| substitute_term_at(v, var, term, depth))
+                .collect(),
+        },
+        Expr::ConstantValue(v) => Expr::ConstantValue(substitute_term_at(v, var, term, depth)),
+        Expr::Equals(l, r) => Expr::Equals(
+            substitute_term_at(l, var, term, depth),
+            substitute_term_at(r, var, term, depth),
+        ),
+        Expr::Not(v) => Expr::Not(rec(v)),
+        Expr::And(l, r) => Expr::And(rec(l), rec(r)),
+        Expr::Or(l, r) => Expr::Or(rec(l), rec(r)),
+        Expr::Xor(l, r) => Expr::Xor(rec(l), rec(r)),
+        Expr::Conditional(l, r) => Expr::Conditional(rec(l), rec(r)),
+        Expr::Biconditional(l, r) => Expr::Biconditional(rec(l), rec(r)),
+        Expr::Universal { iter, expr } => {
+            let (iter, expr) = substitute_quantified(iter, expr, var, term, depth);
+            Expr::Universal { iter, expr }
+        }
+        Expr::Existential { iter, expr } => {
+            let (iter, expr) = substitute_quantified(iter, expr, var, term, depth);
+            Expr::Existential { iter, expr }
+        }
+        Expr::UnknownOperator {
+            left,
+            operator,
+            right,
+        } => Expr::UnknownOperator {
+            left: rec(left),
+            operator: operator.clone(),
+            right: rec(right),
+        },
+        v => v.clone(),
+    }
+}
+
+fn substitute_term_at(
+    expr: &ConstantExpr,
+    var: &Variable,
+    term: &ConstantExpr,
+    depth: usize,
+) -> ConstantExpr {
+    match expr {
+        ConstantExpr::Variable(v) if v.name == var.name => shift_term(term, depth),
+        ConstantExpr::Function { func, args } => ConstantExpr::Function {
+            func: func.clone(),
+            args: args
+                .iter()
+                .map(|v| substitute_term_at(v, var, term, depth))
+                .collect(),
+        },
+        ConstantExpr::Operator(op, left, right) => ConstantExpr::Operator(
+            op.clone(),
+            Box::new(substitute_term_at(left, var, term, depth)),
+            Box::new(substitute_term_at(right, var, term, depth)),
+        ),
+        v => v.clone(),
+    }
+}
+
+/// Adds `by` to the index of every bound variable in `term`, for when it is moved under `by`
+/// more quantifiers
+fn shift_term(term: &ConstantExpr, by: usize) -> ConstantExpr {
+    shift_term_at(term, by as isize, 0)
+}
+
+/// Adds `by` to the index of every variable in `expr` that is bound by a quantifier outside of
+/// it, for when it is moved under `by` more quantifiers. A negative `by` is for when quantifiers
+/// around it are removed.
+pub fn shift(expr: &Expr, by: isize) -> Expr {
+    shift_at(expr, by, 0)
+}
+
+fn shift_at(expr: &Expr, by: isize, depth: usize) -> Expr {
+    let rec = |v: &Expr| Box::new(shift_at(v, by, depth));
+
+    match expr {
+        Expr::Predicate { pred, args } => Expr::Predicate {
+            pred: pred.clone(),
+            args: args.iter().map(|v| shift_term_at(v, by, depth)).collect(),
+        },
+        Expr::ConstantValue(v) => Expr::ConstantValue(shift_term_at(v, by, depth)),
+        Expr::Equals(l, r) => {
+            Expr::Equals(shift_term_at(l, by, depth), shift_term_at(r, by, depth))
+        }
+        Expr::Not(v) => Expr::Not(rec(v)),
+        Expr::And(l, r) => Expr::And(rec(l), rec(r)),
+        Expr::Or(l, r) => Expr::Or(rec(l), rec(r)),
+        Expr::Xor(l, r) => Expr::Xor(rec(l), rec(r)),
+        Expr::Conditional(l, r) => Expr::Conditional(rec(l), rec(r)),
+        Expr::Biconditional(l, r) => Expr::Biconditional(rec(l), rec(r)),
+        Expr::Universal { iter, expr } => Expr::Universal {
+            iter: iter.clone(),
+            expr: Box::new(shift_at(expr, by, depth + 1)),
+        },
+        Expr::Existential { iter, expr } => Expr::Existential {
+            iter: iter.clone(),
+            expr: Box::new(shift_at(expr, by, depth + 1)),
+        },
+        Expr::UnknownOperator {
+            left,
+            operator,
+            right,
+        } => Expr::UnknownOperator {
+            left: rec(left),
+            operator: operator.clone(),
+            right: rec(right),
+        },
+        v => v.clone(),
+    }
+}
+
+fn shift_term_at(term: &ConstantExpr, by: isize, depth: usize) -> ConstantExpr {
+    match term {
+        ConstantExpr::Variable(v) => ConstantExpr::Variable(Variable {
+            name: v.name.clone(),
+            index: v.index.map(|i| {
+                if i >= depth {
+                    i.saturating_add_signed(by)
+                } else {
+                    i
+                }
+            }),
+        }),
+        ConstantExpr::Function { func, args } => ConstantExpr::Function {
+            func: func.clone(),
+            args: args.iter().map(|v| shift_term_at(v, by, depth)).collect(),
+        },
+        ConstantExpr::Operator(op, left, right) => ConstantExpr::Operator(
+            op.clone(),
+            Box::new(shift_term_at(left, by, depth)),
+            Box::new(shift_term_at(right, by, depth)),
+        ),
+        v => v.clone(),
+    }
+}
+
+fn substitute_quantified(
+    iter: &Variable,
+    expr: &Expr,
+    var: &Variable,
+    term: &ConstantExpr,
+    depth: usize,
+) -> (Variable, Box<Expr>) {
+    // var is shadowed, so nothing inside refers to it
+    if iter.name == var.name || !free_variables(expr).contains(&var.name) {
+        return (iter.clone(), Box::new(expr.clone()));
+    }
+
+    let term_vars = free_variables_term(term);
+    if term_vars.contains(&iter.name) {
+        // rename the quantified variable so it doesn't capture anything in term
+        let mut avoid = free_variables(expr);
+        avoid.extend(term_vars);
+        avoid.insert(var.name.clone());
+
+        let renamed = Variable {
+            name: fresh_name(&iter.name, &avoid),
+            ..iter.clone()
+        };
+        let expr = substitute(expr, iter, &ConstantExpr::Variable(renamed.clone()));
+
+        (
+            renamed,
+            Box::new(substitute_at(&expr, var, term, depth + 1)),
+        )
+    } else {
+        (
+            iter.clone(),
+            Box::new(substitute_at(expr, var, term, depth + 1)),
+        )
+    }
+}
+
+/// Finds a variable name based on `name` that isn't in `avoid`
+pub(crate) fn fresh_name(name: &str, avoid: &HashSet<String>) -> String {
+    (1..)
+        .map(|i| format!("{}{}", name, i))
+        .find(|v| !avoid.contains(v))
+        .unwrap()
+}
+
+/// Collects the names of every variable that is not bound by a quantifier in `expr`
+pub fn free_variables(expr: &Expr) -> HashSet<String> {
+    match expr {
+        Expr::Predicate { args, .. } => args.iter().flat_map(free_variables_term).collect(),
+        Expr::ConstantValue(v) => free_variables_term(v),
+        Expr::Equals(l, r) => {
+            let mut vars = free_variables_term(l);
+            vars.extend(free_variables_term(r));
+            vars
+        }
+        Expr::Variable(v) => HashSet::from([v.name.clone()]),
+        Expr::Not(v) => free_variables(v),
+        Expr::And(l, r)
+        | Expr::Or(l, r)
+        | Expr::Xor(l, r)
+        | Expr::Conditional(l, r)
+        | Expr::Biconditional(l, r)
+        | Expr::UnknownOperator {
+            left: l, right: r, ..
+        } => {
+            let mut vars = free_variables(l);
+            vars.extend(free_variables(r));
+            vars
+        }
+        Expr::Universal { iter, expr } | Expr::Existential { iter, expr } => {
+            let mut vars = free_variables(expr);
+            vars.remove(&iter.name);
+            vars
+        }
+        Expr::Literal(_) | Expr::Tautology | Expr::Contradiction | Expr::Invalid => HashSet::new(),
+    }
+}
+
+/// Collects the names of every variable in `expr`
+pub fn free_variables_term(expr: &ConstantExpr) -> HashSet<String> {
+    match expr {
+        ConstantExpr::Variable(v) => HashSet::from([v.name.clone()]),
+        ConstantExpr::Function { args, .. } => args.iter().flat_map(free_variables_term).collect(),
+        ConstantExpr::Operator(_, l, r) => {
+            let mut vars = free_variables_term(l);
+            vars.extend(free_variables_term(r));
+            vars
+        }
+        ConstantExpr::Constant(_) | ConstantExpr::Number(_) => HashSet::new(),
+    }
+}
+
+/// Checks whether two [Expr]s are equal up to the names of their quantified variables,
+/// so `@x P(x)` and `@y P(y)` are considered the same.
+pub fn alpha_eq(a: &Expr, b: &Expr) -> bool {
+    alpha_eq_in(a, b, &mut Vec::new())
+}
+
+/// Checks whether two [ConstantExpr]s are equal; free variables are compared by name
+pub fn alpha_eq_term(a: &ConstantExpr, b: &ConstantExpr) -> bool {
+    alpha_eq_term_in(a, b, &[])
+}
+
+type Bindings<'a> = Vec<(&'a str, &'a str)>;
+
+fn alpha_eq_in<'a>(a: &'a Expr, b: &'a Expr, bound: &mut Bindings<'a>) -> bool {
+    match (a, b) {
+        (
+            Expr::Predicate { pred, args },
+            Expr::Predicate {
+                pred: pred_b,
+                args: args_b,
+            },
+        ) => {
+            pred == pred_b
+                && args.len() == args_b.len()
+                && args
+                    .iter()
+                    .zip(args_b)
+                    .all(|(a, b)| alpha_eq_term_in(a, b, bound))
+        }
+        (Expr::ConstantValue(a), Expr::ConstantValue(b)) => alpha_eq_term_in(a, b, bound),
+        (Expr::Equals(l1, r1), Expr::Equals(l2, r2)) => {
+            alpha_eq_term_in(l1, l2, bound) && alpha_eq_term_in(r1, r2, bound)
+        }
+        (Expr::Variable(a), Expr::Variable(b)) => variables_eq(a, b, bound),
+        (Expr::Not(a), Expr::Not(b)) => alpha_eq_in(a, b, bound),
+        (Expr::And(l1, r1), Expr::And(l2, r2))
+        | (Expr::Or(l1, r1), Expr::Or(l2, r2))
+        | (Expr::Xor(l1, r1), Expr::Xor(l2, r2))
+        | (Expr::Conditional(l1, r1), Expr::Conditional(l2, r2))
+        | (Expr::Biconditional(l1, r1), Expr::Biconditional(l2, r2)) => {
+            alpha_eq_in(l1, l2, bound) && alpha_eq_in(r1, r2, bound)
+        }
+        (
+            Expr::UnknownOperator {
+                left: l1,
+                operator: op1,
+                right: r1,
+            },
+            Expr::UnknownOperator {
+                left: l2,
+                operator: op2,
+                right: r2,
+            },
+        ) => op1 == op2 && alpha_eq_in(l1, l2, bound) && alpha_eq_in(r1, r2, bound),
+        (
+            Expr::Universal { iter, expr },
+            Expr::Universal {
+                iter: iter_b,
+                expr: expr_b,
+            },
+        )
+        | (
+            Expr::Existential { iter, expr },
+            Expr::Existential {
+                iter: iter_b,
+                expr: expr_b,
+            },
+        ) => {
+            bound.push((&iter.name, &iter_b.name));
+            let res = alpha_eq_in(expr, expr_b, bound);
+            bound.pop();
+            res
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn alpha_eq_term_in(a: &ConstantExpr, b: &ConstantExpr, bound: &[(&str, &str)]) -> bool {
+    match (a, b) {
+        (ConstantExpr::Variable(a), ConstantExpr::Variable(b)) => variables_eq(a, b, bound),
+        (
+            ConstantExpr::Function { func, args },
+            ConstantExpr::Function {
+                func: func_b,
+                args: args_b,
+            },
+        ) => {
+            func == func_b
+                && args.len() == args_b.len()
+                && args
+                    .iter()
+                    .zip(args_b)
+                    .all(|(a, b)| alpha_eq_term_in(a, b, bound))
+        }
+        (ConstantExpr::Operator(op1, l1, r1), ConstantExpr::Operator(op2, l2, r2)) => {
+            op1 == op2 && alpha_eq_term_in(l1, l2, bound) && alpha_eq_term_in(r1, r2, bound)
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn variables_eq(a: &Variable, b: &Variable, bound: &[(&str, &str)]) -> bool {
+    // find the innermost quantifier that binds each variable
+    let a_binder = bound.iter().rposition(|(v, _)| *v == a.name);
+    let b_binder = bound.iter().rposition(|(_, v)| *v == b.name);
+
+    match (a_binder, b_binder) {
+        (Some(a_binder), Some(b_binder)) => a_binder == b_binder,
+        (None, None) => a.name == b.name,
+        _ => false,
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+
+use yggdrasil_grammar::expr::Expr;
+
+use crate::{
+    closure::{find_closure, Closure},
+    error::{EngineError, ValidationError},
+    rules::{
+        branch::{check_sub_branches, BranchRule, SubBranchError},
+        identity::{identity_sides, IdentityRule},
+        quantifier::QuantifierRule,
+        rule_for,
+        stack::StackRule,
+        Rule,
+    },
+    substitution::alpha_eq,
+    EngineResult,
+};
+
+/// Identifier of a [Node] that stays the same for as long as the [Tree] exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+/// Identifier of a [Branch] that stays the same for as long as the [Tree] exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BranchId(usize);
+
+/// A non-branching rule that can be used to derive a single statement
+#[derive(Debug, Clone, Copy)]
+pub enum AppliedRule {
+    Stack(StackRule),
+    Quantifier(QuantifierRule),
+    /// Also records the identity statement that was used
+    Identity {
+        rule: IdentityRule,
+        identity: NodeId,
+    },
+}
+
+/// Records which rule a [Node] was derived with, and from which statement
+#[derive(Debug, Clone, Copy)]
+pub struct Annotation {
+    pub rule: AppliedRule,
+    pub source: NodeId,
+}
+
+/// A single statement in a [Tree]
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub id: NodeId,
+    pub branch: BranchId,
+    pub expr: Expr,
+    /// How this statement was derived.
+    /// Premises, and statements at the start of a sub-branch, have no annotation.
+    pub rule: Option<Annotation>,
+}
+
+/// Records the [BranchRule] that split a [Branch] in two
+#[derive(Debug, Clone, Copy)]
+pub struct Split {
+    pub rule: BranchRule,
+    pub source: NodeId,
+    pub children: (BranchId, BranchId),
+}
+
+/// A list of statements, which may split into two sub-branches at the end
+#[derive(Debug, Clone)]
+pub struct Branch {
+    pub id: BranchId,
+    pub parent: Option<BranchId>,
+    pub nodes: Vec<NodeId>,
+    pub split: Option<Split>,
+}
+
+/// A truth tree, independent of how it is displayed or edited
+#[derive(Debug, Clone)]
+pub struct Tree {
+    nodes: Vec<Node>,
+    branches: Vec<Branch>,
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tree {
+    /// Makes a tree with an empty root branch
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            branches: vec![Branch {
+                id: BranchId(0),
+                parent: None,
+                nodes: Vec::new(),
+                split: None,
+            }],
+        }
+    }
+
+    pub fn root(&self) -> BranchId {
+        BranchId(0)
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    pub fn branch(&self, id: BranchId) -> &Branch {
+        &self.branches[id.0]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter()
+    }
+
+    pub fn branches(&self) -> impl Iterator<Item = &Branch> {
+        self.branches.iter()
+    }
+
+    /// Every branch that has not been split
+    pub fn leaves(&self) -> impl Iterator<Item = &Branch> {
+        self.branches.iter().filter(|v| v.split.is_none())
+    }
+
+    /// Adds a statement to the end of a branch
+    pub fn add_node(&mut self, branch: BranchId, expr: Expr, rule: Option<Annotation>) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            id,
+            branch,
+            expr,
+            rule,
+        });
+        self.branches[branch.0].nodes.push(id);
+        id
+    }
+
+    /// Splits a branch into two new, empty sub-branches, by applying `rule` to `source`.
+    /// A branch can only be split once.
+    pub fn split(
+        &mut self,
+        branch: BranchId,
+        rule: BranchRule,
+        source: NodeId,
+    ) -> EngineResult<(BranchId, BranchId)> {
+        if self.branch(branch).split.is_some() {
+            Err(ValidationError::AlreadySplit)?
+        }
+
+        let mut make_child = || {
+            let id = BranchId(self.branches.len());
+            self.branches.push(Branch {
+                id,
+                parent: Some(branch),
+                nodes: Vec::new(),
+                split: None,
+            });
+            id
+        };
+        let children = (make_child(), make_child());
+
+        self.branches[branch.0].split = Some(Split {
+            rule,
+            source,
+            children,
+        });
+        Ok(children)
+    }
+
+    /// Every branch from the root down to `branch`
+    pub fn ancestry(&self, branch: BranchId) -> Vec<BranchId> {
+        let mut ancestry = vec![branch];
+        while let Some(parent) = self.branch(*ancestry.last().unwrap()).parent {
+            ancestry.push(parent);
+        }
+        ancestry.reverse();
+        ancestry
+    }
+
+    /// Every statement from the root down to the end of `branch`
+    pub fn path(&self, branch: BranchId) -> Vec<NodeId> {
+        self.ancestry(branch)
+            .into_iter()
+            .flat_map(|v| self.branch(v).nodes.iter().copied())
+            .collect()
+    }
+
+    /// Every statement above `node` on its path
+    pub fn path_before(&self, node: NodeId) -> Vec<NodeId> {
+        let mut path = self.path(self.node(node).branch);
+        path.truncate(path.iter().position(|v| *v == node).unwrap());
+        path
+    }
+
+    /// Finds a statement on `branch` that `expr` follows from by a single non-branching rule, for
+    /// trees that are written without saying where each statement came from
+    pub fn find_annotation(&self, branch: BranchId, expr: &Expr) -> Option<Annotation> {
+        let path = self.path(branch);
+        let exprs = path.iter().map(|v| &self.node(*v).expr).collect::<Vec<_>>();
+
+        let decomposed = path.iter().zip(&exprs).find_map(|(source, source_expr)| {
+            let rule = match rule_for(source_expr)? {
+                Rule::Stack(rule) => rule
+                    .decompose(source_expr)
+                    .ok()?
+                    .iter()
+                    .any(|v| alpha_eq(v, expr))
+                    .then_some(AppliedRule::Stack(rule))?,
+                Rule::Quantifier(rule) => rule
+                    .check(source_expr, expr, exprs.iter().copied())
+                    .ok()
+                    .map(|_| AppliedRule::Quantifier(rule))?,
+                Rule::Branch(_) => None?,
+            };
+            Some(Annotation {
+                rule,
+                source: *source,
+            })
+        });
+
+        decomposed.or_else(|| {
+            let rule = IdentityRule::SubstitutionOfIdenticals;
+            path.iter()
+                .zip(&exprs)
+                .filter(|(_, v)| identity_sides(v).is_ok())
+                .find_map(|(identity, identity_expr)| {
+                    path.iter()
+                        .zip(&exprs)
+                        .find(|(_, source_expr)| {
+                            rule.check(identity_expr, source_expr, expr).is_ok()
+                        })
+                        .map(|(source, _)| Annotation {
+                            rule: AppliedRule::Identity {
+                                rule,
+                                identity: *identity,
+                            },
+                            source: *source,
+                        })
+                })
+        })
+    }
+
+    /// Adds statements that were written without saying where they came from to the end of an
+    /// empty branch, annotating each one with [Tree::find_annotation]. The statements [check_tree]
+    /// lets go without an annotation are left without one: the premises at the start of the root
+    /// branch, up to the last one that can't be derived, and the results of the branching rule at
+    /// the start of a sub-branch.
+    pub fn add_statements(
+        &mut self,
+        branch: BranchId,
+        exprs: impl IntoIterator<Item = Expr>,
+    ) -> Vec<NodeId> {
+        let results = self.branch(branch).parent.and_then(|parent| {
+            let split = self.branch(parent).split?;
+            let (first, second) = split.rule.decompose(&self.node(split.source).expr).ok()?;
+            Some(if split.children.0 == branch {
+                first.len()
+            } else {
+                second.len()
+            })
+        });
+
+        let nodes = exprs
+            .into_iter()
+            .enumerate()
+            .map(|(i, expr)| {
+                let rule = match results {
+                    Some(results) if i < results => None,
+                    _ => self.find_annotation(branch, &expr),
+                };
+                self.add_node(branch, expr, rule)
+            })
+            .collect::<Vec<_>>();
+
+        if branch == self.root() {
+            let premises = nodes
+                .iter()
+                .rposition(|v| self.node(*v).rule.is_none())
+                .map_or(0, |i| i + 1);
+            for node in &nodes[..premises] {
+                self.nodes[node.0].rule = None;
+            }
+        }
+
+        nodes
+    }
+
+    /// Finds the statements that close `branch`, checking every statement from the root down
+    pub fn closure(&self, branch: BranchId) -> Option<(Closure, Vec<NodeId>)> {
+        let path = self.path(branch);
+        find_closure(path.iter().map(|v| &self.node(*v).expr)).map(|closure| {
+            let nodes = closure.statements().into_iter().map(|i| path[i]).collect();
+            (closure, nodes)
+        })
+    }
+}
+
+/// Checks every rule application in a tree, returning the problems found with each node.
+/// Problems with how a branch was split are reported on the first statement of the sub-branch,
+/// or on the statement the rule was applied to if the sub-branch is empty.
+///
+/// Only the premises at the start of the root branch, and the results of a branching rule at the
+/// start of a sub-branch, can be written without an [Annotation].
+pub fn check_tree(tree: &Tree) -> BTreeMap<NodeId, Vec<EngineError>> {
+    let mut diagnostics: BTreeMap<NodeId, Vec<EngineError>> = BTreeMap::new();
+    let mut report =
+        |node: NodeId, err: EngineError| diagnostics.entry(node).or_default().push(err);
+
+    for branch in tree.branches() {
+        let unannotated = match branch.parent.and_then(|v| tree.branch(v).split) {
+            None => usize::MAX,
+            Some(split) => match split.rule.decompose(&tree.node(split.source).expr) {
+                Ok((first, second)) => first.len().max(second.len()),
+                // the split itself is reported below
+                Err(_) => usize::MAX,
+            },
+        };
+        let leading = branch
+            .nodes
+            .iter()
+            .take_while(|v| tree.node(**v).rule.is_none())
+            .count()
+            .min(unannotated);
+
+        for node in &branch.nodes[leading..] {
+            if tree.node(*node).rule.is_none() {
+                report(*node, ValidationError::MissingRule.into());
+            }
+        }
+    }
+
+    for node in tree.nodes() {
+        let Some(Annotation { rule, source }) = node.rule else {
+            continue;
+        };
+
+        let before = tree.path_before(node.id);
+        if !before.contains(&source) {
+            report(node.id, ValidationError::SourceNotOnPath.into());
+            continue;
+        }
+        let source = &tree.node(source).expr;
+
+        let res = match rule {
+            AppliedRule::Stack(rule) => rule.decompose(source).and_then(|v| {
+                if v.iter().any(|v| alpha_eq(v, &node.expr)) {
+                    Ok(())
+                } else {
+                    Err(ValidationError::InvalidDecomposition(rule.to_string()))?
+                }
+            }),
+            AppliedRule::Quantifier(rule) => rule.check(
+                source,
+                &node.expr,
+                before.iter().map(|v| &tree.node(*v).expr),
+            ),
+            AppliedRule::Identity { rule, identity } => {
+                if before.contains(&identity) {
+                    rule.check(&tree.node(identity).expr, source, &node.expr)
+                } else {
+                    Err(ValidationError::SourceNotOnPath.into())
+                }
+            }
+        };
+
+        if let Err(err) = res {
+            report(node.id, err);
+        }
+    }
+
+    for branch in tree.branches() {
+        let Some(Split {
+            rule,
+            source,
+            children: (first, second),
+        }) = branch.split
+        else {
+            continue;
+        };
+
+        if !tree.path(branch.id).contains(&source) {
+            report(source, ValidationError::SourceNotOnPath.into());
+            continue;
+        }
+
+        let correct = match rule.decompose(&tree.node(source).expr) {
+            Ok(v) => v,
+            Err(err) => {
+                report(source, err);
+                continue;
+            }
+        };
+
+        let statements = |branch: BranchId| {
+            tree.branch(branch)
+                .nodes
+                .iter()
+                .map(|v| Box::new(Some(tree.node(*v).expr.clone())))
+        };
+
+        let wrong = match check_sub_branches(&correct, statements(first), statements(second)) {
+            None => vec![],
+            Some(SubBranchError::First) => vec![first],
+            Some(SubBranchError::Second) => vec![second],
+            Some(SubBranchError::Both) => vec![first, second],
+        };
+
+        for child in wrong {
+            report(
+                tree.branch(child).nodes.first().copied().unwrap_or(source),
+                ValidationError::InvalidSubBranch(rule.to_string()).into(),
+            );
+        }
+    }
+
+    diagnostics
+}
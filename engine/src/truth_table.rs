@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use strum::{Display, EnumIter};
+use yggdrasil_grammar::expr::{Expr, ExprDiscriminants};
+
+use crate::{error::ValidationError, eval::Structure, EngineResult};
+
+/// The most sentence letters a [TruthTable] can have, since each one doubles the number of rows
+pub const MAX_ATOMS: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Display)]
+pub enum Classification {
+    /// True in every row
+    Tautology,
+
+    /// False in every row
+    Contradiction,
+
+    /// True in some rows and false in others
+    Contingent,
+}
+
+/// A single valuation of the sentence letters, and the value of each column under it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// The value of each sentence letter, in the same order as [TruthTable::atoms]
+    pub valuation: Vec<bool>,
+
+    /// The value of each subformula, in the same order as [TruthTable::columns]
+    pub values: Vec<bool>,
+}
+
+/// The truth table of a propositional statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruthTable {
+    /// Every sentence letter in the statement, in alphabetical order
+    pub atoms: Vec<String>,
+
+    /// Every compound subformula of the statement, each after its own subformulas.
+    /// The last column is the statement itself.
+    pub columns: Vec<Expr>,
+
+    /// One row per valuation, starting with every sentence letter true
+    pub rows: Vec<Row>,
+}
+
+impl TruthTable {
+    /// Builds the truth table of a statement made only of sentence letters, connectives, `⊤` and
+    /// `⊥`
+    pub fn new(expr: &Expr) -> EngineResult<Self> {
+        let mut atoms = Vec::new();
+        let mut columns = Vec::new();
+        collect(expr, &mut atoms, &mut columns)?;
+
+        atoms.sort();
+        atoms.dedup();
+        if atoms.len() > MAX_ATOMS {
+            Err(ValidationError::TooManyAtoms(atoms.len(), MAX_ATOMS))?
+        }
+
+        // a statement with no connectives still gets a column for itself
+        if columns.last() != Some(expr) {
+            columns.push(expr.clone());
+        }
+
+        let rows = (0..1usize << atoms.len())
+            .map(|i| {
+                let valuation = (0..atoms.len())
+                    .map(|j| i >> (atoms.len() - 1 - j) & 1 == 0)
+                    .collect::<Vec<_>>();
+
+                let structure = Structure {
+                    atoms: atoms.iter().cloned().zip(valuation.clone()).collect(),
+                    ..Default::default()
+                };
+                let values = columns
+                    .iter()
+                    .map(|v| structure.eval(v))
+                    .collect::<EngineResult<Vec<_>>>()?;
+
+                Ok(Row { valuation, values })
+            })
+            .collect::<EngineResult<Vec<_>>>()?;
+
+        Ok(Self {
+            atoms,
+            columns,
+            rows,
+        })
+    }
+
+    /// The value of the whole statement in each row
+    pub fn results(&self) -> impl Iterator<Item = bool> + '_ {
+        self.rows.iter().map(|v| *v.values.last().unwrap())
+    }
+
+    pub fn classification(&self) -> Classification {
+        match (self.results().any(|v| v), self.results().all(|v| v)) {
+            (_, true) => Classification::Tautology,
+            (false, _) => Classification::Contradiction,
+            _ => Classification::Contingent,
+        }
+    }
+
+    /// The valuation used in a row, as a map from sentence letters to values
+    pub fn valuation(&self, row: usize) -> BTreeMap<String, bool> {
+        self.atoms
+            .iter()
+            .cloned()
+            .zip(self.rows[row].valuation.iter().copied())
+            .collect()
+    }
+}
+
+fn collect(expr: &Expr, atoms: &mut Vec<String>, columns: &mut Vec<Expr>) -> EngineResult {
+    match expr {
+        Expr::Literal(literal) => {
+            atoms.push(literal.0.clone());
+            return Ok(());
+        }
+        Expr::Tautology | Expr::Contradiction => return Ok(()),
+        Expr::Not(inner) => collect(inner, atoms, columns)?,
+        Expr::And(left, right)
+        | Expr::Or(left, right)
+        | Expr::Xor(left, right)
+        | Expr::Conditional(left, right)
+        | Expr::Biconditional(left, right) => {
+            collect(left, atoms, columns)?;
+            collect(right, atoms, columns)?;
+        }
+        expr => Err(ValidationError::NotPropositional(ExprDiscriminants::from(
+            expr,
+        )))?,
+    }
+
+    if !columns.contains(expr) {
+        columns.push(expr.clone());
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use yggdrasil_grammar::expr::{constantexpr::ConstantExpr, Expr};
+
+/// The term each variable is replaced with, by name. Variables not listed are left alone.
+pub type Unifier = BTreeMap<String, ConstantExpr>;
+
+/// Finds the most general substitution of variables that makes `a` and `b` the same term, if
+/// there is one. Every variable is treated as something that can be replaced, so this is meant for
+/// the free variables of a free-variable tableau.
+///
+/// The result is idempotent: no variable it replaces appears in the terms it replaces them with.
+pub fn unify(a: &ConstantExpr, b: &ConstantExpr) -> Option<Unifier> {
+    unify_all([(a, b)])
+}
+
+/// Finds the most general substitution that makes both terms of every pair the same
+pub fn unify_all<'a>(
+    pairs: impl IntoIterator<Item = (&'a ConstantExpr, &'a ConstantExpr)>,
+) -> Option<Unifier> {
+    let mut unifier = Unifier::new();
+    let mut pending = pairs
+        .into_iter()
+        .map(|(a, b)| (a.clone(), b.clone()))
+        .collect::<Vec<_>>();
+
+    while let Some((a, b)) = pending.pop() {
+        let a = apply(&a, &unifier);
+        let b = apply(&b, &unifier);
+
+        match (a, b) {
+            (ConstantExpr::Variable(a), ConstantExpr::Variable(b)) if a.name == b.name => {}
+            (ConstantExpr::Variable(var), term) | (term, ConstantExpr::Variable(var)) => {
+                if occurs(&var.name, &term) {
+                    return None;
+                }
+
+                let binding = Unifier::from([(var.name.clone(), term.clone())]);
+                for v in unifier.values_mut() {
+                    *v = apply(v, &binding);
+                }
+                unifier.insert(var.name, term);
+            }
+            (
+                ConstantExpr::Function { func, args },
+                ConstantExpr::Function {
+                    func: func_b,
+                    args: args_b,
+                },
+            ) => {
+                if func != func_b || args.len() != args_b.len() {
+                    return None;
+                }
+                pending.extend(args.into_iter().zip(args_b));
+            }
+            (ConstantExpr::Operator(op, l1, r1), ConstantExpr::Operator(op_b, l2, r2)) => {
+                if op != op_b {
+                    return None;
+                }
+                pending.extend([(*l1, *l2), (*r1, *r2)]);
+            }
+            (a, b) => {
+                if a != b {
+                    return None;
+                }
+            }
+        }
+    }
+
+    Some(unifier)
+}
+
+/// Finds the most general substitution that makes `a` and the negation of `b` the same, when one
+/// is a [Predicate](Expr::Predicate) and the other is the negation of one. A branch with both on
+/// it can be closed once the substitution is applied.
+pub fn unify_complementary(a: &Expr, b: &Expr) -> Option<Unifier> {
+    let (positive, negative) = match (a, b) {
+        (Expr::Not(a), b) | (b, Expr::Not(a)) => (b, a.as_ref()),
+        _ => return None,
+    };
+
+    match (positive, negative) {
+        (
+            Expr::Predicate { pred, args },
+            Expr::Predicate {
+                pred: pred_b,
+                args: args_b,
+            },
+        ) if pred == pred_b && args.len() == args_b.len() => unify_all(args.iter().zip(args_b)),
+        _ => None,
+    }
+}
+
+/// Replaces every variable in `term` that `unifier` has a term for
+pub fn apply(term: &ConstantExpr, unifier: &Unifier) -> ConstantExpr {
+    match term {
+        ConstantExpr::Variable(v) => unifier
+            .get(&v.name)
+            .cloned()
+            .unwrap_or_else(|| term.clone()),
+        ConstantExpr::Function { func, args } => ConstantExpr::Function {
+            func: func.clone(),
+            args: args.iter().map(|v| apply(v, unifier)).collect(),
+        },
+        ConstantExpr::Operator(op, left, right) => ConstantExpr::Operator(
+            op.clone(),
+            Box::new(apply(left, unifier)),
+            Box::new(apply(right, unifier)),
+        ),
+        v => v.clone(),
+    }
+}
+
+/// Replaces every variable in the terms of `expr` that `unifier` has a term for. Quantifiers are
+/// not treated specially, so this is meant for statements without them.
+pub fn apply_expr(expr: &Expr, unifier: &Unifier) -> Expr {
+    let rec = |v: &Expr| Box::new(apply_expr(v, unifier));
+
+    match expr {
+        Expr::Predicate { pred, args } => Expr::Predicate {
+            pred: pred.clone(),
+            args: args.iter().map(|v| apply(v, unifier)).collect(),
+        },
+        Expr::ConstantValue(v) => Expr::ConstantValue(apply(v, unifier)),
+        Expr::Equals(l, r) => Expr::Equals(apply(l, unifier), apply(r, unifier)),
+        Expr::Not(v) => Expr::Not(rec(v)),
+        Expr::And(l, r) => Expr::And(rec(l), rec(r)),
+        Expr::Or(l, r) => Expr::Or(rec(l), rec(r)),
+        Expr::Xor(l, r) => Expr::Xor(rec(l), rec(r)),
+        Expr::Conditional(l, r) => Expr::Conditional(rec(l), rec(r)),
+        Expr::Biconditional(l, r) => Expr::Biconditional(rec(l), rec(r)),
+        v => v.clone(),
+    }
+}
+
+/// Checks whether the variable `name` appears in `term`
+fn occurs(name: &str, term: &ConstantExpr) -> bool {
+    match term {
+        ConstantExpr::Variable(v) => v.name == name,
+        ConstantExpr::Function { args, .. } => args.iter().any(|v| occurs(name, v)),
+        ConstantExpr::Operator(_, left, right) => occurs(name, left) || occurs(name, right),
+        ConstantExpr::Constant(_) | ConstantExpr::Number(_) => false,
+    }
+}
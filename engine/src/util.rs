@@ -1,47 +1,129 @@
-use std::{
-    collections::HashSet,
-    hash::{DefaultHasher, Hash, Hasher},
-    ops::Deref,
-};
+use std::{collections::HashSet, ops::Deref};
 
-use yggdrasil_grammar::expr::Expr;
+use crate::substitution::alpha_eq;
+use yggdrasil_grammar::expr::{
+    constantexpr::{Constant, ConstantExpr},
+    Expr,
+};
 
+/// Checks whether the first items of `list` are every statement in `set`, in any order.
+/// Statements are compared with [alpha_eq].
 pub fn expr_list_starts_with<'a, L: Iterator<Item = &'a Expr>>(
     mut list: L,
     set: &HashSet<Expr>,
 ) -> bool {
+    let set = set.iter().collect::<Vec<_>>();
+    let mut matched = vec![false; set.len()];
     let mut found = 0;
+
     while let (Some(v), false) = (list.next(), found >= set.len()) {
-        if set.contains(v) {
-            found += 1;
-        } else {
-            return false;
+        match find_unmatched(&set, &matched, v) {
+            Some(i) => {
+                found += 1;
+                matched[i] = true;
+            }
+            None => return false,
         }
     }
     found >= set.len()
 }
 
+/// Like [expr_list_starts_with], but statements in `list` that failed to parse never match
 pub fn expr_maybe_list_starts_with(
     mut list: impl Iterator<Item = impl Deref<Target = Option<Expr>>>,
     set: &HashSet<Box<Expr>>,
 ) -> bool {
+    let set = set.iter().map(|v| v.as_ref()).collect::<Vec<_>>();
+    let mut matched = vec![false; set.len()];
     let mut found = 0;
-    let mut matched = HashSet::new();
 
     while let (Some(v), false) = (list.next(), found >= set.len()) {
-        let h = {
-            let mut h = DefaultHasher::new();
-            v.hash(&mut h);
-            h.finish()
-        };
-
-        match v.deref() {
-            Some(v) if set.contains(v) && !matched.contains(&h) => {
+        match v
+            .deref()
+            .as_ref()
+            .and_then(|v| find_unmatched(&set, &matched, v))
+        {
+            Some(i) => {
                 found += 1;
-                matched.insert(h);
+                matched[i] = true;
             }
-            _ => return false,
+            None => return false,
         }
     }
     found >= set.len()
 }
+
+fn find_unmatched(set: &[&Expr], matched: &[bool], v: &Expr) -> Option<usize> {
+    set.iter()
+        .zip(matched)
+        .position(|(s, matched)| !matched && alpha_eq(s, v))
+}
+
+/// Collects every [Constant] used as a term in an [Expr]
+pub fn expr_constants(expr: &Expr) -> HashSet<Constant> {
+    fn walk_term(term: &ConstantExpr, found: &mut HashSet<Constant>) {
+        match term {
+            ConstantExpr::Constant(c) => {
+                found.insert(c.clone());
+            }
+            ConstantExpr::Function { args, .. } => {
+                args.iter().for_each(|v| walk_term(v, found));
+            }
+            ConstantExpr::Operator(_, left, right) => {
+                walk_term(left, found);
+                walk_term(right, found);
+            }
+            ConstantExpr::Variable(_) | ConstantExpr::Number(_) => (),
+        }
+    }
+
+    fn walk(expr: &Expr, found: &mut HashSet<Constant>) {
+        match expr {
+            Expr::Predicate { args, .. } => args.iter().for_each(|v| walk_term(v, found)),
+            Expr::ConstantValue(term) => walk_term(term, found),
+            Expr::Equals(left, right) => {
+                walk_term(left, found);
+                walk_term(right, found);
+            }
+            Expr::Not(inner) => walk(inner, found),
+            Expr::And(left, right)
+            | Expr::Or(left, right)
+            | Expr::Xor(left, right)
+            | Expr::Conditional(left, right)
+            | Expr::Biconditional(left, right)
+            | Expr::UnknownOperator { left, right, .. } => {
+                walk(left, found);
+                walk(right, found);
+            }
+            Expr::Universal { expr, .. } | Expr::Existential { expr, .. } => walk(expr, found),
+            Expr::Literal(_)
+            | Expr::Variable(_)
+            | Expr::Tautology
+            | Expr::Contradiction
+            | Expr::Invalid => (),
+        }
+    }
+
+    let mut found = HashSet::new();
+    walk(expr, &mut found);
+    found
+}
+
+/// Every list of `len` items from `items`, in lexicographic order
+pub fn tuples<'a, T: Clone + 'a>(
+    items: impl IntoIterator<Item = &'a T> + Copy,
+    len: usize,
+) -> Vec<Vec<T>> {
+    (0..len).fold(vec![vec![]], |tuples, _| {
+        tuples
+            .into_iter()
+            .flat_map(|tuple| {
+                items.into_iter().map(move |v| {
+                    let mut tuple = tuple.clone();
+                    tuple.push(v.clone());
+                    tuple
+                })
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,31 @@
+use yggdrasil_engine::closure::{find_closure, Closure};
+use yggdrasil_grammar::{expr::Expr, Parser, PARSER};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+fn closure_of(path: &[&str]) -> Option<Closure> {
+    let path = path.iter().map(|v| parse(v)).collect::<Vec<_>>();
+    find_closure(&path)
+}
+
+#[test]
+fn closure() {
+    assert_eq!(
+        closure_of(&["P -> Q", "P", "Q", "~P"]),
+        Some(Closure::Contradictory(1, 3))
+    );
+    assert_eq!(
+        closure_of(&["~(@x F(x))", "@y F(y)"]),
+        Some(Closure::Contradictory(0, 1))
+    );
+    assert_eq!(closure_of(&["P", "⊥"]), Some(Closure::SelfContradictory(1)));
+    assert_eq!(closure_of(&["~⊤"]), Some(Closure::SelfContradictory(0)));
+    assert_eq!(
+        closure_of(&["P", "f(a) != f(a)"]),
+        Some(Closure::SelfContradictory(1))
+    );
+
+    assert_eq!(closure_of(&["P", "~Q", "a != b"]), None);
+}
@@ -0,0 +1,81 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use yggdrasil_engine::{
+    countermodel::{countermodel, is_countermodel},
+    error::EvaluationError,
+    eval::{Object, Structure},
+    prover::{prove, ProofStatus, ProverConfig},
+    EngineResult,
+};
+use yggdrasil_grammar::{expr::Expr, Parser, PARSER};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+fn named(name: &str) -> Object {
+    Object::Named(name.to_string())
+}
+
+fn try_countermodel_of(premises: &[&str], conclusion: &str) -> EngineResult<Structure> {
+    let premises = premises.iter().map(|v| parse(v)).collect::<Vec<_>>();
+    let conclusion = parse(conclusion);
+    let proof = prove(&premises, &conclusion, &ProverConfig::default());
+
+    let ProofStatus::Open(branch) = proof.status else {
+        panic!("expected an open branch, found {:?}", proof.status);
+    };
+    let model = countermodel(&proof.tree, branch)?;
+    assert_eq!(is_countermodel(&model, &premises, &conclusion), Ok(true));
+    Ok(model)
+}
+
+fn countermodel_of(premises: &[&str], conclusion: &str) -> Structure {
+    try_countermodel_of(premises, conclusion).unwrap()
+}
+
+#[test]
+fn countermodels() {
+    let model = countermodel_of(&["P -> Q", "Q"], "P");
+    assert_eq!(
+        model.atoms,
+        BTreeMap::from([("P".to_string(), false), ("Q".to_string(), true)])
+    );
+
+    let model = countermodel_of(&["/x F(x)", "/x G(x)"], "/x (F(x) & G(x))");
+    assert_eq!(model.domain.len(), 2);
+    assert_eq!(model.predicates["F"].len(), 1);
+    assert_eq!(model.predicates["G"].len(), 1);
+    assert_ne!(model.predicates["F"], model.predicates["G"]);
+
+    // identical constants are the same object
+    let model = countermodel_of(&["a = b", "F(a)"], "G(b)");
+    assert_eq!(model.domain, BTreeSet::from([named("a")]));
+    assert_eq!(model.constants["b"], named("a"));
+
+    // and so are function terms applied to them
+    let model = countermodel_of(&["a = b", "P(f(a))", "Q(f(b))"], "R(a)");
+    assert_eq!(model.domain, BTreeSet::from([named("a"), named("f(a)")]));
+    assert_eq!(model.functions["f"][&vec![named("a")]], named("f(a)"));
+
+    let model = countermodel_of(&["F(f(a))"], "F(a)");
+    assert_eq!(model.functions["f"][&vec![named("a")]], named("f(a)"));
+    assert_eq!(model.functions["f"][&vec![named("f(a)")]], named("a"));
+    assert_eq!(model.eval(&parse("@x F(f(x))")), Ok(false));
+
+    // numbers refer to themselves, including the results of arithmetic on them
+    let model = countermodel_of(&["F(1 + 2)"], "G(3)");
+    assert!(model.domain.contains(&Object::Number(3)));
+    assert_eq!(
+        model.predicates["F"],
+        BTreeSet::from([vec![Object::Number(3)]])
+    );
+
+    // but the tree rules know nothing about arithmetic on anything else, or numbers being
+    // identical to other objects, so no structure can be read off those branches
+    assert!(try_countermodel_of(&["a + b = c"], "P").is_err());
+    assert_eq!(
+        try_countermodel_of(&["a = 1"], "P"),
+        Err(EvaluationError::NotSatisfied("a = 1".to_string()).into())
+    );
+}
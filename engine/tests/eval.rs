@@ -0,0 +1,84 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use yggdrasil_engine::{
+    error::{EngineError, EvaluationError},
+    eval::{Object, Structure},
+};
+use yggdrasil_grammar::{expr::Expr, Parser, PARSER};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+fn named(name: &str) -> Object {
+    Object::Named(name.to_string())
+}
+
+#[test]
+fn eval() {
+    let mut structure = Structure::new([named("a"), named("b")]);
+    structure.constants = BTreeMap::from([
+        ("a".to_string(), named("a")),
+        ("b".to_string(), named("b")),
+        ("c".to_string(), named("a")),
+    ]);
+    structure.functions = BTreeMap::from([(
+        "f".to_string(),
+        BTreeMap::from([
+            (vec![named("a")], named("b")),
+            (vec![named("b")], named("a")),
+        ]),
+    )]);
+    structure.predicates = BTreeMap::from([("F".to_string(), BTreeSet::from([vec![named("a")]]))]);
+    structure.atoms = BTreeMap::from([("P".to_string(), true)]);
+
+    let eval = |input: &str| structure.eval(&parse(input));
+
+    assert_eq!(eval("P & ~Q"), Ok(true));
+    assert_eq!(eval("P -> Q"), Ok(false));
+    assert_eq!(eval("⊤ ⊕ ⊥"), Ok(true));
+    assert_eq!(eval("F(a) & ~F(b)"), Ok(true));
+    assert_eq!(eval("F(f(b))"), Ok(true));
+    assert_eq!(eval("c = a"), Ok(true));
+    assert_eq!(eval("c != b"), Ok(true));
+    assert_eq!(eval("/x F(x)"), Ok(true));
+    assert_eq!(eval("@x F(x)"), Ok(false));
+    assert_eq!(eval("@x (F(x) | F(f(x)))"), Ok(true));
+    assert_eq!(eval("@x (/y (f(x) = y))"), Ok(true));
+    assert_eq!(eval("@x (/y (~(x = y)))"), Ok(true));
+    assert_eq!(eval("/x (@y (x = y))"), Ok(false));
+
+    assert_eq!(
+        eval("F(d)"),
+        Err(EngineError::EvaluationError(
+            EvaluationError::UnknownConstant("d".to_string())
+        ))
+    );
+    assert_eq!(
+        eval("a < b"),
+        Err(EngineError::EvaluationError(EvaluationError::NotANumber(
+            "<".to_string(),
+            "a".to_string()
+        )))
+    );
+}
+
+#[test]
+fn eval_numbers() {
+    let structure = Structure::new((0..4).map(Object::Number));
+    let eval = |input: &str| structure.eval(&parse(input));
+
+    assert_eq!(eval("(2 + 3) = 5"), Ok(true));
+    assert_eq!(eval("(2 ^ 3) > 7"), Ok(true));
+    assert_eq!(eval("@x (x < 4)"), Ok(true));
+    assert_eq!(eval("/x ((x * x) = 9)"), Ok(true));
+    assert_eq!(eval("/x ((x * x) = 2)"), Ok(false));
+    assert_eq!(eval("1' = 2"), Ok(true));
+    assert_eq!(eval("@x (x' > x)"), Ok(true));
+    assert_eq!(
+        eval("(1 / 0) = 0"),
+        Err(EngineError::EvaluationError(
+            EvaluationError::ArithmeticError("1 / 0".to_string())
+        ))
+    );
+}
@@ -0,0 +1,71 @@
+use yggdrasil_engine::{
+    herbrand::{signature, HerbrandBase, HerbrandUniverse},
+    skolem::skolemize,
+};
+use yggdrasil_grammar::{
+    expr::{constantexpr::ConstantExpr, Expr},
+    Parser, PARSER,
+};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+fn term(input: &str) -> ConstantExpr {
+    match parse(&format!("F({})", input)) {
+        Expr::Predicate { mut args, .. } => args.remove(0),
+        v => panic!("expected a predicate, found {:?}", v),
+    }
+}
+
+#[test]
+fn skolemization() {
+    let cases = [
+        ("/x (F(x))", "F(sk1)"),
+        ("@x (/y (F(x) & G(y)))", "@x (F(x) & G(sk1(x)))"),
+        ("@x (/y (@z (F(y) & G(z))))", "@x (@z (F(sk1(x)) & G(z)))"),
+        ("~(@x (F(x)))", "~F(sk1)"),
+        // names that are already used are skipped
+        ("/x (F(x) & G(sk1))", "F(sk2) & G(sk1)"),
+        (
+            "(/x (F(x))) & (@y (/z (G(y) -> H(z))))",
+            "F(sk1) & (@y (~G(y) | H(sk2(y))))",
+        ),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(skolemize(&parse(input)), parse(expected), "{}", input);
+    }
+}
+
+#[test]
+fn herbrand_universe() {
+    let exprs = [parse("F(a)"), parse("@x (G(f(x)))")];
+    let symbols = signature(&exprs);
+    assert!(!symbols.is_finite());
+
+    let terms = HerbrandUniverse::new(&symbols).take(4).collect::<Vec<_>>();
+    assert_eq!(terms, ["a", "f(a)", "f(f(a))", "f(f(f(a)))"].map(term));
+
+    let atoms = HerbrandBase::new(&symbols).take(4).collect::<Vec<_>>();
+    assert_eq!(atoms, ["F(a)", "G(a)", "F(f(a))", "G(f(a))"].map(parse));
+
+    let exprs = [parse("F(a) -> P"), parse("G(b)")];
+    let symbols = signature(&exprs);
+    assert!(symbols.is_finite());
+    assert_eq!(
+        HerbrandUniverse::new(&symbols).collect::<Vec<_>>(),
+        ["a", "b"].map(term)
+    );
+    assert_eq!(
+        HerbrandBase::new(&symbols).collect::<Vec<_>>(),
+        ["P", "F(a)", "G(a)", "F(b)", "G(b)"].map(parse)
+    );
+
+    // a constant is made up when there aren't any
+    let symbols = signature(&[parse("@x (F(x))")]);
+    assert_eq!(
+        HerbrandUniverse::new(&symbols).collect::<Vec<_>>(),
+        ["a"].map(term)
+    );
+}
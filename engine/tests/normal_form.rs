@@ -0,0 +1,129 @@
+use yggdrasil_engine::{
+    normal_form::{cnf, dnf, eliminate_connectives, nnf, prenex, tseitin, Transformation},
+    sat::{equivalent, is_satisfiable},
+    substitution::alpha_eq,
+};
+use yggdrasil_grammar::{expr::Expr, Parser, PARSER};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+/// Checks whether a statement is made of `outer`s of clauses, which are `inner`s of atoms
+fn is_normal(
+    expr: &Expr,
+    outer: fn(&Expr) -> Option<(&Expr, &Expr)>,
+    inner: fn(&Expr) -> Option<(&Expr, &Expr)>,
+) -> bool {
+    fn atomic(expr: &Expr) -> bool {
+        match expr {
+            Expr::Not(v) => matches!(v.as_ref(), Expr::Literal(_)),
+            v => matches!(v, Expr::Literal(_) | Expr::Tautology | Expr::Contradiction),
+        }
+    }
+    fn clause(expr: &Expr, inner: fn(&Expr) -> Option<(&Expr, &Expr)>) -> bool {
+        inner(expr).map_or_else(
+            || atomic(expr),
+            |(l, r)| clause(l, inner) && clause(r, inner),
+        )
+    }
+
+    outer(expr).map_or_else(
+        || clause(expr, inner),
+        |(l, r)| is_normal(l, outer, inner) && is_normal(r, outer, inner),
+    )
+}
+
+fn and(expr: &Expr) -> Option<(&Expr, &Expr)> {
+    match expr {
+        Expr::And(l, r) => Some((l, r)),
+        _ => None,
+    }
+}
+
+fn or(expr: &Expr) -> Option<(&Expr, &Expr)> {
+    match expr {
+        Expr::Or(l, r) => Some((l, r)),
+        _ => None,
+    }
+}
+
+#[test]
+fn propositional_normal_forms() {
+    let expr = parse("(P <-> Q) ⊕ ~(R -> (P & S))");
+
+    let derivation = eliminate_connectives(&expr);
+    assert_eq!(derivation.start, expr);
+    assert_eq!(
+        derivation.steps[0].transformation,
+        Transformation::XorElimination
+    );
+    assert!(equivalent(&expr, derivation.result()).unwrap().holds);
+
+    let derivation = nnf(&expr);
+    assert!(equivalent(&expr, derivation.result()).unwrap().holds);
+    assert!(derivation
+        .steps
+        .iter()
+        .any(|v| v.transformation == Transformation::DeMorgan));
+
+    // every step keeps the statement equivalent
+    let derivation = cnf(&expr);
+    for step in &derivation.steps {
+        assert!(equivalent(&expr, &step.result).unwrap().holds);
+    }
+    assert!(is_normal(derivation.result(), and, or));
+
+    let derivation = dnf(&expr);
+    assert!(equivalent(&expr, derivation.result()).unwrap().holds);
+    assert!(is_normal(derivation.result(), or, and));
+
+    assert_eq!(
+        cnf(&parse("P | (Q & R)")).result(),
+        &parse("(P | Q) & (P | R)")
+    );
+    assert_eq!(nnf(&parse("~(P & ~Q)")).result(), &parse("~P | Q"));
+}
+
+#[test]
+fn tseitin_encoding() {
+    let expr = parse("(P <-> Q) ⊕ ~(R -> (P & S))");
+    let derivation = tseitin(&expr).unwrap();
+
+    assert_eq!(derivation.steps.len(), 4);
+    assert!(is_normal(derivation.result(), and, or));
+    assert!(is_satisfiable(derivation.result()).unwrap().holds);
+
+    let contradiction = parse("(P & Q) & ~(Q | P)");
+    let derivation = tseitin(&contradiction).unwrap();
+    assert!(is_normal(derivation.result(), and, or));
+    assert!(!is_satisfiable(derivation.result()).unwrap().holds);
+
+    assert!(tseitin(&parse("@x F(x)")).is_err());
+}
+
+#[test]
+fn prenex_normal_form() {
+    let derivation = prenex(&parse("(@x F(x)) -> (/y G(y))"));
+    assert!(alpha_eq(
+        derivation.result(),
+        &parse("/x (/y (~F(x) | G(y)))")
+    ));
+
+    // the second x has to be renamed so it doesn't capture the first
+    let derivation = prenex(&parse("(@x F(x)) & (/x G(x))"));
+    assert!(derivation
+        .steps
+        .iter()
+        .any(|v| v.transformation == Transformation::VariableRenaming));
+    assert!(alpha_eq(
+        derivation.result(),
+        &parse("@x (/y (F(x) & G(y)))")
+    ));
+
+    // indices are kept up to date as quantifiers are moved
+    assert_eq!(
+        prenex(&parse("@z ((@x F(x)) & G(z))")).result(),
+        &parse("@z (@x (F(x) & G(z)))")
+    );
+}
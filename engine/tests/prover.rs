@@ -0,0 +1,110 @@
+use yggdrasil_engine::{
+    completeness::{check_completeness, Verdict},
+    prover::{prove, ProofStatus, ProverConfig},
+    tree::check_tree,
+};
+use yggdrasil_grammar::{expr::Expr, Parser, PARSER};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+fn prove_str(premises: &[&str], conclusion: &str) -> (ProofStatus, Verdict) {
+    let premises = premises.iter().map(|v| parse(v)).collect::<Vec<_>>();
+    let proof = prove(&premises, &parse(conclusion), &ProverConfig::default());
+
+    assert!(check_tree(&proof.tree).is_empty());
+    (proof.status, check_completeness(&proof.tree).verdict)
+}
+
+#[test]
+fn prover() {
+    assert_eq!(
+        prove_str(&["P -> Q", "P"], "Q"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+    assert_eq!(
+        prove_str(&["(P <-> Q) & (Q <-> R)"], "P <-> R"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+    assert_eq!(
+        prove_str(&["@x (F(x) -> G(x))", "/x F(x)"], "/x G(x)"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+    assert_eq!(
+        prove_str(&["~@x F(x)"], "/x (~F(x))"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+
+    assert_eq!(
+        prove_str(&["a = b", "F(g(a))"], "F(g(b))"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+    assert_eq!(
+        prove_str(&["a = b"], "b = a"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+    assert_eq!(
+        prove_str(&["a = b", "b = c"], "a = c"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+    assert_eq!(
+        prove_str(&["@x (f(x) = x)", "F(f(f(a)))"], "F(a)"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+
+    // the universal that keeps introducing constants doesn't starve the other one, whichever
+    // comes first
+    assert_eq!(
+        prove_str(&["@x /y R(x, y)", "@x F(x)"], "F(a)"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+    assert_eq!(
+        prove_str(&["@x F(x)", "@x /y R(x, y)"], "F(a)"),
+        (ProofStatus::Closed, Verdict::Valid)
+    );
+
+    let (status, verdict) = prove_str(&["a = b", "F(a)"], "G(b)");
+    assert!(matches!(status, ProofStatus::Open(branch) if verdict == Verdict::Invalid(branch)));
+
+    let (status, verdict) = prove_str(&["P | Q"], "P");
+    assert!(matches!(status, ProofStatus::Open(branch) if verdict == Verdict::Invalid(branch)));
+
+    let (status, verdict) = prove_str(&["/x F(x)", "/x G(x)"], "/x (F(x) & G(x))");
+    assert!(matches!(status, ProofStatus::Open(branch) if verdict == Verdict::Invalid(branch)));
+}
+
+#[test]
+fn prover_limits() {
+    // every instance of the universal introduces a new constant to instantiate it with
+    let proof = prove(
+        &[parse("@x /y (F(x) & G(y))")],
+        &parse("P"),
+        &ProverConfig {
+            max_depth: 100,
+            max_instantiations: 10,
+        },
+    );
+
+    assert_eq!(proof.status, ProofStatus::LimitReached);
+    assert!(check_tree(&proof.tree).is_empty());
+    assert_eq!(check_completeness(&proof.tree).verdict, Verdict::Incomplete);
+}
+
+#[test]
+fn prover_fresh_constants() {
+    // enough new constants that they run out of letters, which must still print as constants
+    let proof = prove(
+        &[parse("@x /y (F(x) & G(y))")],
+        &parse("P"),
+        &ProverConfig::default(),
+    );
+
+    for node in proof.tree.nodes() {
+        assert_eq!(parse(&node.expr.to_string()), node.expr);
+    }
+    assert!(proof
+        .tree
+        .nodes()
+        .any(|v| v.expr.to_string().contains("a1")));
+}
@@ -0,0 +1,251 @@
+use std::collections::HashSet;
+
+use yggdrasil_engine::{
+    closure::is_self_contradictory,
+    rules::{
+        branch::BranchRule, identity::IdentityRule, quantifier::QuantifierRule, stack::StackRule,
+    },
+    substitution::{alpha_eq, substitute},
+};
+use yggdrasil_grammar::{
+    expr::{
+        constantexpr::{Constant, ConstantExpr},
+        literal::Literal,
+        variable::Variable,
+        Expr,
+    },
+    Parser, PARSER,
+};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+fn lit(name: &str) -> Box<Expr> {
+    Box::new(Expr::Literal(Literal(name.to_string())))
+}
+
+fn not(expr: Box<Expr>) -> Box<Expr> {
+    Box::new(Expr::Not(expr))
+}
+
+#[test]
+fn stack_rules() {
+    let and = Expr::And(lit("P"), lit("Q"));
+    assert_eq!(
+        StackRule::And.decompose(&and).unwrap(),
+        HashSet::from([lit("P"), lit("Q")])
+    );
+
+    let nor = Expr::Not(Box::new(Expr::Or(lit("P"), lit("Q"))));
+    assert_eq!(
+        StackRule::Nor.decompose(&nor).unwrap(),
+        HashSet::from([not(lit("P")), not(lit("Q"))])
+    );
+
+    let not_conditional = Expr::Not(Box::new(Expr::Conditional(lit("P"), lit("Q"))));
+    assert_eq!(
+        StackRule::NotConditional
+            .decompose(&not_conditional)
+            .unwrap(),
+        HashSet::from([lit("P"), not(lit("Q"))])
+    );
+
+    let double_negation = Expr::Not(not(lit("P")));
+    assert_eq!(
+        StackRule::DoubleNegation
+            .decompose(&double_negation)
+            .unwrap(),
+        HashSet::from([lit("P")])
+    );
+
+    assert!(StackRule::And.decompose(&nor).is_err());
+    assert!(StackRule::DoubleNegation.decompose(&and).is_err());
+}
+
+#[test]
+fn xor_rules() {
+    let xor = Expr::Xor(lit("P"), lit("Q"));
+    assert_eq!(
+        BranchRule::Xor.decompose(&xor).unwrap(),
+        (
+            HashSet::from([lit("P"), not(lit("Q"))]),
+            HashSet::from([not(lit("P")), lit("Q")])
+        )
+    );
+
+    let not_xor = Expr::Not(Box::new(xor.clone()));
+    assert_eq!(
+        BranchRule::NotXor.decompose(&not_xor).unwrap(),
+        (
+            HashSet::from([lit("P"), lit("Q")]),
+            HashSet::from([not(lit("P")), not(lit("Q"))])
+        )
+    );
+
+    assert!(BranchRule::Xor.decompose(&not_xor).is_err());
+    assert!(BranchRule::NotXor.decompose(&xor).is_err());
+}
+
+#[test]
+fn quantifier_rules() {
+    let universal = parse("@x (P(x) & Q(x, b))");
+    assert!(QuantifierRule::UniversalInstantiation
+        .check(&universal, &parse("P(a) & Q(a, b)"), [&universal])
+        .is_ok());
+    assert!(QuantifierRule::UniversalInstantiation
+        .check(&universal, &parse("P(a) & Q(c, b)"), [&universal])
+        .is_err());
+
+    let existential = parse("/x P(x)");
+    let a = ConstantExpr::Constant(Constant("a".to_string()));
+    assert_eq!(
+        QuantifierRule::ExistentialInstantiation
+            .decompose(&existential, Some(&a), [&existential])
+            .unwrap(),
+        parse("P(a)")
+    );
+
+    // a is already on the branch, so it can't be used for existential instantiation
+    let used = parse("Q(a)");
+    assert!(QuantifierRule::ExistentialInstantiation
+        .check(&existential, &parse("P(a)"), [&existential, &used])
+        .is_err());
+    assert!(QuantifierRule::ExistentialInstantiation
+        .check(&existential, &parse("P(b)"), [&existential, &used])
+        .is_ok());
+
+    let not_universal = parse("~(@x P(x))");
+    let Expr::Not(inner) = &not_universal else {
+        unreachable!()
+    };
+    let Expr::Universal { iter, expr } = inner.as_ref() else {
+        unreachable!()
+    };
+    assert_eq!(
+        QuantifierRule::NotUniversal
+            .decompose(&not_universal, None, [])
+            .unwrap(),
+        Expr::Existential {
+            iter: iter.clone(),
+            expr: Box::new(Expr::Not(expr.clone()))
+        }
+    );
+    assert!(QuantifierRule::NotExistential
+        .decompose(&not_universal, None, [])
+        .is_err());
+}
+
+#[test]
+fn identity_rules() {
+    let rule = IdentityRule::SubstitutionOfIdenticals;
+    let identity = parse("a = f(b)");
+    let expr = parse("F(a) & G(g(f(b)), a)");
+
+    assert_eq!(
+        rule.decompose(&identity, &expr).unwrap(),
+        HashSet::from([
+            Box::new(parse("F(f(b)) & G(g(f(b)), f(b))")),
+            Box::new(parse("F(a) & G(g(a), a)")),
+        ])
+    );
+
+    // any number of occurrences can be replaced, in either direction
+    assert!(rule
+        .check(&identity, &expr, &parse("F(a) & G(g(f(b)), f(b))"))
+        .is_ok());
+    assert!(rule
+        .check(&identity, &expr, &parse("F(a) & G(g(a), a)"))
+        .is_ok());
+    assert!(rule
+        .check(&identity, &parse("~(a = b)"), &parse("~(f(b) = b)"))
+        .is_ok());
+    assert!(rule
+        .check(&identity, &expr, &parse("F(f(b)) & G(g(a), a)"))
+        .is_err());
+    assert!(rule
+        .check(&identity, &expr, &parse("F(b) & G(g(f(b)), a)"))
+        .is_err());
+    assert!(rule
+        .check(&parse("F(a)"), &expr, &parse("F(a) & G(g(a), a)"))
+        .is_err());
+
+    assert!(is_self_contradictory(&parse("~(f(a) = f(a))")));
+    assert!(is_self_contradictory(&parse("g(a) != g(a)")));
+    assert!(!is_self_contradictory(&parse("~(f(a) = f(b))")));
+}
+
+#[test]
+fn substitution() {
+    let x = Variable {
+        name: "x".to_string(),
+        index: None,
+    };
+    let y = ConstantExpr::Variable(Variable {
+        name: "y".to_string(),
+        index: None,
+    });
+
+    // P(x) & @x Q(x): only the free x is replaced
+    let expr = Expr::And(
+        Box::new(Expr::Predicate {
+            pred: Literal("P".to_string()),
+            args: vec![ConstantExpr::Variable(x.clone())],
+        }),
+        Box::new(Expr::Universal {
+            iter: x.clone(),
+            expr: Box::new(Expr::Predicate {
+                pred: Literal("Q".to_string()),
+                args: vec![ConstantExpr::Variable(x.clone())],
+            }),
+        }),
+    );
+    let a = ConstantExpr::Constant(Constant("a".to_string()));
+    assert!(alpha_eq(
+        &substitute(&expr, &x, &a),
+        &parse("P(a) & (@x Q(x))")
+    ));
+
+    // /y R(x, y): substituting y for x must not be captured by the quantifier
+    let expr = Expr::Existential {
+        iter: Variable {
+            name: "y".to_string(),
+            index: Some(0),
+        },
+        expr: Box::new(Expr::Predicate {
+            pred: Literal("R".to_string()),
+            args: vec![ConstantExpr::Variable(x.clone()), y.clone()],
+        }),
+    };
+    let Expr::Existential { iter, expr: inner } = substitute(&expr, &x, &y) else {
+        unreachable!()
+    };
+    assert_ne!(iter.name, "y");
+    assert_eq!(
+        *inner,
+        Expr::Predicate {
+            pred: Literal("R".to_string()),
+            args: vec![y, ConstantExpr::Variable(iter)],
+        }
+    );
+}
+
+#[test]
+fn alpha_equivalence() {
+    assert!(alpha_eq(&parse("@x P(x)"), &parse("@y P(y)")));
+    assert!(alpha_eq(
+        &parse("@x (/y R(x, y))"),
+        &parse("@y (/x R(y, x))")
+    ));
+    assert!(!alpha_eq(
+        &parse("@x (/y R(x, y))"),
+        &parse("@x (/y R(y, x))")
+    ));
+    assert!(!alpha_eq(&parse("@x P(x)"), &parse("/x P(x)")));
+
+    // instances are compared up to renaming, even across separately parsed statements
+    let nested = parse("@x (/y R(x, y))");
+    assert!(QuantifierRule::UniversalInstantiation
+        .check(&nested, &parse("/z R(a, z)"), [&nested])
+        .is_ok());
+}
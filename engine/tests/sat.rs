@@ -0,0 +1,75 @@
+use yggdrasil_engine::{
+    error::{EngineError, ValidationError},
+    eval::Structure,
+    sat::{entails, equivalent, is_satisfiable, is_valid, Valuation},
+};
+use yggdrasil_grammar::{
+    expr::{Expr, ExprDiscriminants},
+    Parser, PARSER,
+};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+fn eval(valuation: &Valuation, input: &str) -> bool {
+    let structure = Structure {
+        atoms: valuation.clone(),
+        ..Default::default()
+    };
+    structure.eval(&parse(input)).unwrap()
+}
+
+#[test]
+fn sat() {
+    let answer = is_satisfiable(&parse("((P | Q) & ~P) & (Q -> R)")).unwrap();
+    assert!(answer.holds);
+    assert!(eval(&answer.witness.unwrap(), "((P | Q) & ~P) & (Q -> R)"));
+
+    let answer = is_satisfiable(&parse("(P <-> Q) & (P ⊕ Q)")).unwrap();
+    assert!(!answer.holds);
+    assert_eq!(answer.witness, None);
+
+    assert!(
+        is_valid(&parse("((P -> Q) & (Q -> R)) -> (P -> R)"))
+            .unwrap()
+            .holds
+    );
+    assert!(is_valid(&parse("⊤ | ⊥")).unwrap().holds);
+
+    let answer = is_valid(&parse("(P -> Q) -> (Q -> P)")).unwrap();
+    assert!(!answer.holds);
+    assert!(!eval(&answer.witness.unwrap(), "(P -> Q) -> (Q -> P)"));
+
+    let premises = [parse("P -> Q"), parse("~Q")];
+    assert!(entails(&premises, &parse("~P")).unwrap().holds);
+
+    let answer = entails(&premises, &parse("R")).unwrap();
+    assert!(!answer.holds);
+    let witness = answer.witness.unwrap();
+    assert!(eval(&witness, "((P -> Q) & ~Q) & ~R"));
+
+    assert!(
+        equivalent(&parse("~(P & Q)"), &parse("~P | ~Q"))
+            .unwrap()
+            .holds
+    );
+    assert!(
+        equivalent(&parse("P ⊕ Q"), &parse("~(P <-> Q)"))
+            .unwrap()
+            .holds
+    );
+    let answer = equivalent(&parse("P -> Q"), &parse("Q -> P")).unwrap();
+    assert!(!answer.holds);
+    assert_ne!(
+        eval(answer.witness.as_ref().unwrap(), "P -> Q"),
+        eval(answer.witness.as_ref().unwrap(), "Q -> P")
+    );
+
+    assert_eq!(
+        is_valid(&parse("@x F(x)")),
+        Err(EngineError::ValidationError(
+            ValidationError::NotPropositional(ExprDiscriminants::Universal)
+        ))
+    );
+}
@@ -0,0 +1,211 @@
+use yggdrasil_engine::{
+    closure::Closure,
+    completeness::{check_completeness, Verdict},
+    error::ValidationError,
+    rules::{branch::BranchRule, quantifier::QuantifierRule, stack::StackRule},
+    tree::{check_tree, Annotation, AppliedRule, Tree},
+};
+use yggdrasil_grammar::{expr::Expr, Parser, PARSER};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+#[test]
+fn check_tree_diagnostics() {
+    let mut tree = Tree::new();
+    let root = tree.root();
+
+    let and = tree.add_node(root, parse("(P | Q) & R"), None);
+    let all = tree.add_node(root, parse("@x F(x)"), None);
+    let or = tree.add_node(
+        root,
+        parse("P | Q"),
+        Some(Annotation {
+            rule: AppliedRule::Stack(StackRule::And),
+            source: and,
+        }),
+    );
+    let wrong = tree.add_node(
+        root,
+        parse("Q"),
+        Some(Annotation {
+            rule: AppliedRule::Stack(StackRule::And),
+            source: and,
+        }),
+    );
+    let instance = tree.add_node(
+        root,
+        parse("F(a)"),
+        Some(Annotation {
+            rule: AppliedRule::Quantifier(QuantifierRule::UniversalInstantiation),
+            source: all,
+        }),
+    );
+
+    let (left, right) = tree.split(root, BranchRule::Or, or).unwrap();
+    assert_eq!(
+        tree.split(root, BranchRule::Or, or).unwrap_err(),
+        ValidationError::AlreadySplit.into()
+    );
+    let p = tree.add_node(left, parse("P"), None);
+    let not_q = tree.add_node(right, parse("~Q"), None);
+
+    let diagnostics = check_tree(&tree);
+    assert!(diagnostics.contains_key(&wrong));
+    assert!(diagnostics.contains_key(&not_q));
+    assert!(!diagnostics.contains_key(&instance));
+    assert!(!diagnostics.contains_key(&p));
+    assert_eq!(diagnostics.len(), 2);
+
+    assert_eq!(tree.path(left), vec![and, all, or, wrong, instance, p]);
+    assert!(tree.closure(left).is_none());
+
+    // a statement from another branch can't be used
+    tree.add_node(
+        right,
+        parse("~P"),
+        Some(Annotation {
+            rule: AppliedRule::Stack(StackRule::DoubleNegation),
+            source: p,
+        }),
+    );
+    assert_eq!(check_tree(&tree).len(), 3);
+
+    // only the results of the split can start the sub-branch without a rule
+    let not_p = tree.add_node(left, parse("~P"), None);
+    assert_eq!(
+        check_tree(&tree).get(&not_p),
+        Some(&vec![ValidationError::MissingRule.into()])
+    );
+    assert_eq!(
+        tree.closure(left),
+        Some((Closure::Contradictory(5, 6), vec![p, not_p]))
+    );
+
+    // a statement without a rule after a derived one isn't a premise
+    let mut tree = Tree::new();
+    let root = tree.root();
+    let and = tree.add_node(root, parse("P & Q"), None);
+    tree.add_node(
+        root,
+        parse("Q"),
+        Some(Annotation {
+            rule: AppliedRule::Stack(StackRule::And),
+            source: and,
+        }),
+    );
+    let made_up = tree.add_node(root, parse("R"), None);
+    assert_eq!(
+        check_tree(&tree).into_keys().collect::<Vec<_>>(),
+        vec![made_up]
+    );
+}
+
+#[test]
+fn completeness() {
+    // P -> Q, P, ~Q: valid once the conditional is decomposed
+    let mut tree = Tree::new();
+    let root = tree.root();
+    let conditional = tree.add_node(root, parse("P -> Q"), None);
+    tree.add_node(root, parse("P"), None);
+    tree.add_node(root, parse("~Q"), None);
+
+    let report = check_completeness(&tree);
+    assert_eq!(report.verdict, Verdict::Incomplete);
+    assert_eq!(report.undecomposed.len(), 1);
+    assert_eq!(report.undecomposed[0].node, conditional);
+
+    let (left, right) = tree
+        .split(root, BranchRule::Conditional, conditional)
+        .unwrap();
+    tree.add_node(left, parse("~P"), None);
+    tree.add_node(right, parse("Q"), None);
+    let report = check_completeness(&tree);
+    assert_eq!(report.verdict, Verdict::Valid);
+    assert!(report.undecomposed.is_empty());
+
+    // @x (F(x) -> G(x)), G(a), F(b): the universal has to be instantiated for both a and b
+    let mut tree = Tree::new();
+    let root = tree.root();
+    tree.add_node(root, parse("@x (F(x) -> G(x))"), None);
+    tree.add_node(root, parse("G(a)"), None);
+    tree.add_node(root, parse("F(b)"), None);
+    tree.add_node(root, parse("F(a) -> G(a)"), None);
+    assert_eq!(check_completeness(&tree).verdict, Verdict::Incomplete);
+
+    let instance = tree.add_node(root, parse("F(b) -> G(b)"), None);
+    assert_eq!(check_completeness(&tree).verdict, Verdict::Incomplete);
+
+    let (left, right) = tree.split(root, BranchRule::Conditional, instance).unwrap();
+    tree.add_node(left, parse("~F(b)"), None);
+    tree.add_node(right, parse("G(b)"), None);
+    assert_eq!(check_completeness(&tree).verdict, Verdict::Invalid(right));
+}
+
+#[test]
+fn find_annotation() {
+    let mut tree = Tree::new();
+    let root = tree.root();
+    let and = tree.add_node(root, parse("P & a = b"), None);
+    let all = tree.add_node(root, parse("@x F(x)"), None);
+    let g = tree.add_node(root, parse("G(a)"), None);
+    tree.add_node(root, parse("a = b"), None);
+
+    let annotation = tree.find_annotation(root, &parse("P")).unwrap();
+    assert!(matches!(
+        annotation.rule,
+        AppliedRule::Stack(StackRule::And)
+    ));
+    assert_eq!(annotation.source, and);
+
+    let annotation = tree.find_annotation(root, &parse("F(a)")).unwrap();
+    assert!(matches!(
+        annotation.rule,
+        AppliedRule::Quantifier(QuantifierRule::UniversalInstantiation)
+    ));
+    assert_eq!(annotation.source, all);
+    tree.add_node(root, parse("F(a)"), Some(annotation));
+
+    let annotation = tree.find_annotation(root, &parse("G(b)")).unwrap();
+    assert!(matches!(annotation.rule, AppliedRule::Identity { .. }));
+    assert_eq!(annotation.source, g);
+    tree.add_node(root, parse("G(b)"), Some(annotation));
+
+    assert!(tree.find_annotation(root, &parse("Q")).is_none());
+    assert!(check_tree(&tree).is_empty());
+}
+
+#[test]
+fn add_statements() {
+    // premises are never annotated, even if they follow from an earlier premise
+    let mut tree = Tree::new();
+    let root = tree.root();
+    let nodes = tree.add_statements(root, ["@x F(x)", "F(a)", "G(b)"].map(parse));
+    assert!(nodes.iter().all(|v| tree.node(*v).rule.is_none()));
+    assert!(check_tree(&tree).is_empty());
+
+    // but the statements after the last premise are
+    let mut tree = Tree::new();
+    let root = tree.root();
+    let nodes = tree.add_statements(root, ["P & Q", "P | S", "P", "Q"].map(parse));
+    assert!(tree.node(nodes[1]).rule.is_none());
+    assert!(matches!(
+        tree.node(nodes[2]).rule,
+        Some(Annotation {
+            rule: AppliedRule::Stack(StackRule::And),
+            ..
+        })
+    ));
+    assert!(tree.node(nodes[3]).rule.is_some());
+
+    // as are the statements after the results of a split
+    let (left, right) = tree.split(root, BranchRule::Or, nodes[1]).unwrap();
+    let left = tree.add_statements(left, ["P", "Q"].map(parse));
+    let right = tree.add_statements(right, ["S", "P"].map(parse));
+    assert!(tree.node(left[0]).rule.is_none());
+    assert!(tree.node(left[1]).rule.is_some());
+    assert!(tree.node(right[0]).rule.is_none());
+    assert!(tree.node(right[1]).rule.is_some());
+    assert!(check_tree(&tree).is_empty());
+}
@@ -0,0 +1,52 @@
+use yggdrasil_engine::{
+    error::{EngineError, ValidationError},
+    truth_table::{Classification, TruthTable},
+};
+use yggdrasil_grammar::{
+    expr::{Expr, ExprDiscriminants},
+    Parser, PARSER,
+};
+
+fn parse(input: &str) -> Expr {
+    PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+}
+
+#[test]
+fn truth_table() {
+    let table = TruthTable::new(&parse("(Q -> P) & ~P")).unwrap();
+    assert_eq!(table.atoms, vec!["P", "Q"]);
+    assert_eq!(
+        table.columns,
+        vec![parse("Q -> P"), parse("~P"), parse("(Q -> P) & ~P")]
+    );
+    assert_eq!(
+        table
+            .rows
+            .iter()
+            .map(|v| (v.valuation.clone(), v.values.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (vec![true, true], vec![true, false, false]),
+            (vec![true, false], vec![true, false, false]),
+            (vec![false, true], vec![false, true, false]),
+            (vec![false, false], vec![true, true, true]),
+        ]
+    );
+    assert_eq!(table.classification(), Classification::Contingent);
+
+    let classify = |input: &str| TruthTable::new(&parse(input)).unwrap().classification();
+    assert_eq!(classify("P | ~P"), Classification::Tautology);
+    assert_eq!(
+        classify("(P <-> Q) ⊕ (P <-> Q)"),
+        Classification::Contradiction
+    );
+    assert_eq!(classify("⊤"), Classification::Tautology);
+    assert_eq!(classify("P"), Classification::Contingent);
+
+    assert_eq!(
+        TruthTable::new(&parse("F(a) | P")),
+        Err(EngineError::ValidationError(
+            ValidationError::NotPropositional(ExprDiscriminants::Predicate)
+        ))
+    );
+}
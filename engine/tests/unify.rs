@@ -0,0 +1,105 @@
+use yggdrasil_engine::unify::{apply, unify, unify_all, unify_complementary, Unifier};
+use yggdrasil_grammar::expr::{
+    constantexpr::{Constant, ConstantExpr},
+    literal::Literal,
+    variable::Variable,
+    Expr,
+};
+
+fn c(name: &str) -> ConstantExpr {
+    ConstantExpr::Constant(Constant(name.to_string()))
+}
+
+fn v(name: &str) -> ConstantExpr {
+    ConstantExpr::Variable(Variable {
+        name: name.to_string(),
+        index: None,
+    })
+}
+
+fn f(name: &str, args: impl IntoIterator<Item = ConstantExpr>) -> ConstantExpr {
+    ConstantExpr::Function {
+        func: Constant(name.to_string()),
+        args: args.into_iter().collect(),
+    }
+}
+
+fn op(op: &str, left: ConstantExpr, right: ConstantExpr) -> ConstantExpr {
+    ConstantExpr::Operator(op.to_string(), Box::new(left), Box::new(right))
+}
+
+fn pred(name: &str, args: impl IntoIterator<Item = ConstantExpr>) -> Expr {
+    Expr::Predicate {
+        pred: Literal(name.to_string()),
+        args: args.into_iter().collect(),
+    }
+}
+
+#[test]
+fn unification() {
+    assert_eq!(unify(&c("a"), &c("a")), Some(Unifier::new()));
+    assert_eq!(unify(&c("a"), &c("b")), None);
+    assert_eq!(
+        unify(&v("x"), &c("a")),
+        Some(Unifier::from([("x".to_string(), c("a"))]))
+    );
+
+    // f(x, g(y)) and f(g(z), x) unify with x = g(z) and y = z
+    let a = f("f", [v("x"), f("g", [v("y")])]);
+    let b = f("f", [f("g", [v("z")]), v("x")]);
+    let unifier = unify(&a, &b).unwrap();
+    assert_eq!(apply(&a, &unifier), apply(&b, &unifier));
+    assert_eq!(unifier.len(), 2);
+    assert_eq!(unifier["x"], f("g", [unifier["y"].clone()]));
+
+    // the result is idempotent
+    let unifier = unify_all([(&v("x"), &v("y")), (&v("y"), &f("h", [c("a")]))]).unwrap();
+    assert_eq!(unifier["x"], f("h", [c("a")]));
+    assert_eq!(unifier["y"], f("h", [c("a")]));
+
+    // occurs check
+    assert_eq!(unify(&v("x"), &f("f", [v("x")])), None);
+    assert_eq!(
+        unify(
+            &f("f", [v("x"), v("x")]),
+            &f("f", [v("y"), f("g", [v("y")])])
+        ),
+        None
+    );
+
+    // clashes
+    assert_eq!(unify(&f("f", [v("x")]), &f("g", [v("x")])), None);
+    assert_eq!(unify(&f("f", [v("x")]), &f("f", [v("x"), v("y")])), None);
+    assert_eq!(
+        unify(&op("+", v("x"), c("a")), &op("*", c("b"), c("a"))),
+        None
+    );
+
+    let unifier = unify(&op("+", v("x"), c("a")), &op("+", c("b"), v("y"))).unwrap();
+    assert_eq!(
+        unifier,
+        Unifier::from([("x".to_string(), c("b")), ("y".to_string(), c("a"))])
+    );
+}
+
+#[test]
+fn complementary_literals() {
+    let positive = pred("F", [v("x"), f("f", [c("a")])]);
+    let negative = Expr::Not(Box::new(pred("F", [c("b"), v("y")])));
+
+    let unifier = unify_complementary(&positive, &negative).unwrap();
+    assert_eq!(
+        unifier,
+        Unifier::from([
+            ("x".to_string(), c("b")),
+            ("y".to_string(), f("f", [c("a")]))
+        ])
+    );
+    assert_eq!(unify_complementary(&negative, &positive), Some(unifier));
+
+    assert_eq!(unify_complementary(&positive, &positive), None);
+    assert_eq!(
+        unify_complementary(&positive, &Expr::Not(Box::new(pred("G", [v("x"), v("y")])))),
+        None
+    );
+}
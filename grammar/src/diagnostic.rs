@@ -0,0 +1,291 @@
+use crate::error::YggError;
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+/// How serious a [Diagnostic] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The statement can't be used as written
+    Error,
+
+    /// The statement can be used, but probably doesn't say what was meant
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// What kind of problem a [Diagnostic] is about. Each has a code that won't change, so other
+/// tools can recognize them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+    /// The statement doesn't follow the grammar
+    Syntax,
+
+    /// A variable is used outside of any quantifier that introduces it
+    UnboundVariable,
+
+    /// Operators that aren't associative are chained without parentheses
+    NotAssociative,
+
+    /// Something that looks like a relation isn't one
+    UnknownRelation,
+
+    /// A number is too big to be used
+    InvalidNumber,
+
+    /// There is more input after the statement
+    UnexpectedInput,
+
+    /// Something that looks like an operator between statements isn't a connective
+    UnknownOperator,
+
+    /// An operator is missing the statement after it
+    MissingOperand,
+
+    /// A bracket is opened but never closed
+    UnclosedBracket,
+}
+
+impl DiagnosticCode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Syntax => "E001",
+            Self::UnboundVariable => "E002",
+            Self::NotAssociative => "E003",
+            Self::UnknownRelation => "E004",
+            Self::InvalidNumber => "E005",
+            Self::UnexpectedInput => "E006",
+            Self::UnknownOperator => "E007",
+            Self::MissingOperand => "E008",
+            Self::UnclosedBracket => "E009",
+        }
+    }
+}
+
+impl Display for DiagnosticCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// A part of the statement a [Diagnostic] points at, in bytes
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    pub span: Range<usize>,
+    pub message: Option<String>,
+}
+
+/// A replacement of part of the statement, in bytes. Empty spans insert the replacement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edit {
+    pub span: Range<usize>,
+    pub replacement: String,
+}
+
+/// A change to the statement that would fix a [Diagnostic]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    pub message: String,
+    pub edits: Vec<Edit>,
+}
+
+impl Suggestion {
+    /// Makes every edit of this suggestion to `source`
+    pub fn apply(&self, source: &str) -> String {
+        let mut edits = self.edits.iter().collect::<Vec<_>>();
+        edits.sort_by_key(|v| (v.span.start, v.span.end));
+
+        let mut out = source.to_string();
+        for edit in edits.into_iter().rev() {
+            out.replace_range(edit.span.clone(), &edit.replacement);
+        }
+        out
+    }
+}
+
+/// A part of the statement to underline in an editor
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Squiggle {
+    pub span: Range<usize>,
+    pub severity: Severity,
+
+    /// Whether this is the main part of the statement the problem is about
+    pub primary: bool,
+
+    /// What to show when the squiggle is hovered
+    pub message: String,
+}
+
+/// A problem found in a statement, along with where it is and how it could be fixed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    pub severity: Severity,
+    pub message: String,
+
+    /// Where the problem is
+    pub primary: Label,
+
+    /// Other parts of the statement involved in the problem
+    pub secondary: Vec<Label>,
+
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    /// Writes this diagnostic out for a terminal or a log, pointing into `source`
+    pub fn render_plain(&self, source: &str) -> String {
+        self.render(source, false)
+    }
+
+    /// Like [Diagnostic::render_plain], but coloured with ANSI escape codes
+    pub fn render_ansi(&self, source: &str) -> String {
+        self.render(source, true)
+    }
+
+    /// The parts of the statement to underline, primary label first
+    pub fn squiggles(&self) -> Vec<Squiggle> {
+        [(&self.primary, true)]
+            .into_iter()
+            .chain(self.secondary.iter().map(|v| (v, false)))
+            .map(|(label, primary)| Squiggle {
+                span: label.span.clone(),
+                severity: self.severity,
+                primary,
+                message: label
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| self.message.clone()),
+            })
+            .collect()
+    }
+
+    fn render(&self, source: &str, colour: bool) -> String {
+        let paint = |text: &str, style: &str| {
+            if colour {
+                format!("\x1b[{}m{}\x1b[0m", style, text)
+            } else {
+                text.to_string()
+            }
+        };
+        let severity_style = match self.severity {
+            Severity::Error => "1;31",
+            Severity::Warning => "1;33",
+        };
+        let gutter_style = "1;34";
+
+        let labels = [(&self.primary, true)]
+            .into_iter()
+            .chain(self.secondary.iter().map(|v| (v, false)))
+            .map(|(label, primary)| (label, primary, line_of(source, label.span.start)))
+            .collect::<Vec<_>>();
+        let width = labels
+            .iter()
+            .map(|(_, _, (line, _))| (line + 1).to_string().len())
+            .max()
+            .unwrap_or(1);
+        let gutter = |text: &str| paint(&format!("{:>width$} |", text), gutter_style);
+
+        let start = self.primary.span.start.min(source.len());
+        let (line, line_start) = line_of(source, start);
+        let mut out = format!(
+            "{}: {}\n{} {}:{}\n{}\n",
+            paint(&format!("{}[{}]", self.severity, self.code), severity_style),
+            paint(&self.message, "1"),
+            paint(&format!("{:>width$}-->", ""), gutter_style),
+            line + 1,
+            source[line_start..start].chars().count() + 1,
+            gutter(""),
+        );
+
+        let mut shown_line = None;
+        for (label, primary, (line, line_start)) in labels {
+            let line_text = source[line_start..].lines().next().unwrap_or_default();
+            if shown_line != Some(line) {
+                out += &format!("{} {}\n", gutter(&(line + 1).to_string()), line_text);
+                shown_line = Some(line);
+            }
+
+            let start = label.span.start.min(line_start + line_text.len());
+            let end = label.span.end.clamp(start, line_start + line_text.len());
+            let markers =
+                (if primary { "^" } else { "-" }).repeat(source[start..end].chars().count().max(1));
+            let marker_style = if primary {
+                severity_style
+            } else {
+                gutter_style
+            };
+
+            out += &format!(
+                "{} {}{}\n",
+                gutter(""),
+                " ".repeat(source[line_start..start].chars().count()),
+                paint(
+                    &match &label.message {
+                        Some(message) => format!("{} {}", markers, message),
+                        None => markers,
+                    },
+                    marker_style
+                ),
+            );
+        }
+
+        for note in &self.notes {
+            out += &format!("{} {} {}\n", gutter(""), paint("= note:", "1"), note);
+        }
+        for suggestion in &self.suggestions {
+            out += &format!(
+                "{} {} {}: {}\n",
+                gutter(""),
+                paint("= help:", "1"),
+                suggestion.message,
+                suggestion.apply(source)
+            );
+        }
+
+        out
+    }
+}
+
+/// Finds the line `offset` is on, and the offset that line starts at
+fn line_of(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    (
+        before.matches('\n').count(),
+        before.rfind('\n').map_or(0, |v| v + 1),
+    )
+}
+
+impl<T: PartialEq + Display> From<&YggError<'_, T>> for Diagnostic {
+    fn from(value: &YggError<'_, T>) -> Self {
+        let mut spans = value.spans().map(|v| v.into_range());
+
+        Self {
+            code: value.code(),
+            severity: Severity::Error,
+            message: value.to_string(),
+            primary: Label {
+                span: spans.next().unwrap_or_else(|| *value.start()..*value.end()),
+                message: None,
+            },
+            secondary: spans
+                .map(|span| Label {
+                    span,
+                    message: None,
+                })
+                .collect(),
+            notes: value.notes().to_vec(),
+            suggestions: value.suggestions().to_vec(),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+/// Which way to group an unparenthesized chain of one operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChainNesting {
+    /// `A ∧ B ∧ C` is `(A ∧ B) ∧ C`
+    Left,
+
+    /// `A ∧ B ∧ C` is `A ∧ (B ∧ C)`
+    Right,
+}
+
+/// Settings for the notation the parser accepts, for following the conventions of a textbook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dialect {
+    /// How chains of ∧ or of ∨ like `A ∧ B ∧ C` are grouped. With [None], they are rejected and
+    /// need parentheses. Chains that mix operators, like `A ∧ B ∨ C`, are always rejected.
+    pub chains: Option<ChainNesting>,
+}
@@ -1,3 +1,4 @@
+use crate::diagnostic::{DiagnosticCode, Suggestion};
 use chumsky::{
     error::{Error, RichPattern, RichReason},
     label::LabelError,
@@ -6,21 +7,28 @@ use chumsky::{
     util::MaybeRef,
 };
 use std::{
-    cmp::{max, min},
+    cmp::min,
     convert::From,
     fmt::{self, Debug, Display, Formatter},
     ops::Range,
 };
 
 pub struct YggError<'a, T, O = usize, S = SimpleSpan<O>> {
+    code: DiagnosticCode,
     spans: Vec<S>,
     start: O,
     end: O,
     reason: Box<RichReason<'a, T>>,
-    context: Vec<(RichPattern<'a, T>, S)>,
+    // boxed slices keep the error small, since it is returned by every parser
+    notes: Box<[String]>,
+    suggestions: Box<[Suggestion]>,
 }
 
 impl<T, O, S> YggError<'_, T, O, S> {
+    pub fn code(&self) -> DiagnosticCode {
+        self.code
+    }
+
     pub fn spans(&self) -> impl Iterator<Item = &S> {
         self.spans.iter()
     }
@@ -36,13 +44,35 @@ impl<T, O, S> YggError<'_, T, O, S> {
     pub fn end(&self) -> &O {
         &self.end
     }
+
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
+    pub fn with_note<M: ToString>(mut self, note: M) -> Self {
+        let mut notes = self.notes.into_vec();
+        notes.push(note.to_string());
+        self.notes = notes.into();
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        let mut suggestions = self.suggestions.into_vec();
+        suggestions.push(suggestion);
+        self.suggestions = suggestions.into();
+        self
+    }
 }
 
 impl<T, O: PartialEq + Ord + Default, S: Into<Range<O>> + Clone> YggError<'_, T, O, S>
 where
     Range<O>: From<S>,
 {
-    pub fn custom<M: ToString>(spans: Vec<S>, msg: M) -> Self {
+    pub fn custom<M: ToString>(code: DiagnosticCode, spans: Vec<S>, msg: M) -> Self {
         let start = spans.iter().fold(None, |a, v| match a {
             None => Some(Range::from(v.clone()).start),
             Some(a) => Some(min(a, Range::from(v.clone()).start)),
@@ -54,11 +84,13 @@ where
         });
 
         Self {
+            code,
             spans,
             reason: Box::new(RichReason::Custom(msg.to_string())),
-            context: Vec::new(),
             start: start.unwrap_or_default(),
             end: end.unwrap_or_default(),
+            notes: Box::default(),
+            suggestions: Box::default(),
         }
     }
 }
@@ -109,14 +141,16 @@ where
         span: I::Span,
     ) -> Self {
         Self {
+            code: DiagnosticCode::Syntax,
             spans: vec![span.clone()],
             reason: Box::new(RichReason::ExpectedFound {
                 expected: expected.into_iter().map(|tok| tok.into()).collect(),
                 found,
             }),
-            context: Vec::new(),
             start: span.clone().start(),
             end: span.end(),
+            notes: Box::default(),
+            suggestions: Box::default(),
         }
     }
 }
@@ -1,5 +1,9 @@
+pub mod connective;
 pub mod constantexpr;
 pub mod literal;
+pub mod operator;
+pub mod relation;
+pub mod spanned;
 pub mod variable;
 
 use constantexpr::ConstantExpr;
@@ -66,6 +70,11 @@ pub enum Expr {
     },
 
     #[cfg_attr(feature = "discriminants", strum(message = "a = b"))]
+    Equals(ConstantExpr, ConstantExpr),
+
+    /// A term on its own, which is an atomic statement when it is a
+    /// [relation](relation::Relation) like `a < b`
+    #[cfg_attr(feature = "discriminants", strum(message = "a < b"))]
     ConstantValue(ConstantExpr),
 
     UnknownOperator {
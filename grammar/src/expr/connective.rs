@@ -0,0 +1,96 @@
+/// An operator that joins two statements, like `P ∧ Q`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Connective {
+    /// What the connective is called, for messages
+    pub name: &'static str,
+
+    /// The symbol this connective is usually written with
+    pub symbol: &'static str,
+
+    /// Every symbol this connective can be written with, including [Connective::symbol]
+    pub aliases: &'static [&'static str],
+
+    /// Symbols other textbooks use for this connective, which aren't accepted but are suggested
+    /// as a replacement for [Connective::symbol]
+    pub lookalikes: &'static [&'static str],
+}
+
+pub const AND: Connective = Connective {
+    name: "conjunction",
+    symbol: "∧",
+    aliases: &["∧", "*", "&"],
+    lookalikes: &["^", "&&", ".", "·", "and"],
+};
+
+pub const OR: Connective = Connective {
+    name: "disjunction",
+    symbol: "∨",
+    aliases: &["∨", "+", "|"],
+    lookalikes: &["v", "||", "or"],
+};
+
+pub const XOR: Connective = Connective {
+    name: "exclusive disjunction",
+    symbol: "⊕",
+    aliases: &["⊕", "!∨", "!+", "!|"],
+    lookalikes: &["⊻", "≢", "!=", "≠", "xor"],
+};
+
+pub const CONDITIONAL: Connective = Connective {
+    name: "conditional",
+    symbol: "→",
+    aliases: &["→", "->"],
+    lookalikes: &["=>", "⇒", "⊃", "implies"],
+};
+
+pub const BICONDITIONAL: Connective = Connective {
+    name: "biconditional",
+    symbol: "↔",
+    aliases: &["↔", "<->"],
+    lookalikes: &["<=>", "⇔", "≡", "==", "iff"],
+};
+
+/// Every connective that can be written between two statements
+pub const CONNECTIVES: &[Connective] = &[AND, OR, XOR, CONDITIONAL, BICONDITIONAL];
+
+/// Finds the connective that was most likely meant by an operator that isn't one: either one of
+/// its [lookalikes](Connective::lookalikes), or the one with an alias that is only a character
+/// or so away from `operator`
+pub fn suggest_connective(operator: &str) -> Option<&'static Connective> {
+    CONNECTIVES
+        .iter()
+        .find(|v| v.lookalikes.contains(&operator))
+        .or_else(|| {
+            CONNECTIVES
+                .iter()
+                .flat_map(|connective| {
+                    connective.aliases.iter().map(move |alias| {
+                        (connective, alias.chars().count(), distance(operator, alias))
+                    })
+                })
+                // an alias that has to be completely rewritten isn't similar
+                .filter(|(_, len, distance)| distance < len)
+                .min_by_key(|(_, _, distance)| *distance)
+                .map(|(connective, _, _)| connective)
+        })
+}
+
+/// The number of characters that have to be added, removed, or replaced to turn `a` into `b`
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            current.push(
+                (previous[j] + usize::from(a != *b))
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
@@ -0,0 +1,76 @@
+/// How a term operator is written, and what it builds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fixity {
+    /// Between two terms, grouping to the left like `(a - b) - c`
+    InfixLeft,
+
+    /// Between two terms, grouping to the right like `a ^ (b ^ c)`
+    InfixRight,
+
+    /// After a term, building an application of the named function, like `a'` for `s(a)`
+    Postfix(&'static str),
+}
+
+/// An operator that builds a term out of other terms, like `a + b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TermOperator {
+    /// The symbol used for this operator in a parsed
+    /// [ConstantExpr::Operator](super::constantexpr::ConstantExpr::Operator)
+    pub symbol: &'static str,
+
+    /// Every symbol this operator can be written with, including [TermOperator::symbol]
+    pub aliases: &'static [&'static str],
+
+    /// Operators with a higher precedence are grouped first, so `a + b × c` is `a + (b × c)`.
+    /// Every operator with the same precedence must have the same [Fixity].
+    pub precedence: u8,
+
+    pub fixity: Fixity,
+}
+
+/// Every operator that can be used in a term: the usual arithmetic operators, and `'` for the
+/// successor function `s` of Peano arithmetic
+pub const TERM_OPERATORS: &[TermOperator] = &[
+    TermOperator {
+        symbol: "+",
+        aliases: &["+"],
+        precedence: 1,
+        fixity: Fixity::InfixLeft,
+    },
+    TermOperator {
+        symbol: "-",
+        aliases: &["-", "−"],
+        precedence: 1,
+        fixity: Fixity::InfixLeft,
+    },
+    TermOperator {
+        symbol: "*",
+        aliases: &["*", "×", "·"],
+        precedence: 2,
+        fixity: Fixity::InfixLeft,
+    },
+    TermOperator {
+        symbol: "/",
+        aliases: &["/", "÷"],
+        precedence: 2,
+        fixity: Fixity::InfixLeft,
+    },
+    TermOperator {
+        symbol: "%",
+        aliases: &["%"],
+        precedence: 2,
+        fixity: Fixity::InfixLeft,
+    },
+    TermOperator {
+        symbol: "^",
+        aliases: &["^"],
+        precedence: 3,
+        fixity: Fixity::InfixRight,
+    },
+    TermOperator {
+        symbol: "'",
+        aliases: &["'", "′"],
+        precedence: 4,
+        fixity: Fixity::Postfix("s"),
+    },
+];
@@ -0,0 +1,35 @@
+/// An infix predicate between two terms, like `a < b`. Identity has its own
+/// [Expr::Equals](super::Expr::Equals), so it isn't one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Relation {
+    /// The symbol used for this relation in a parsed [Expr](super::Expr)
+    pub symbol: &'static str,
+
+    /// Every symbol this relation can be written with, including [Relation::symbol]
+    pub aliases: &'static [&'static str],
+}
+
+/// Every relation that can be written between two terms
+pub const RELATIONS: &[Relation] = &[
+    Relation {
+        symbol: "<",
+        aliases: &["<"],
+    },
+    Relation {
+        symbol: ">",
+        aliases: &[">"],
+    },
+    Relation {
+        symbol: "≤",
+        aliases: &["≤", "<="],
+    },
+    Relation {
+        symbol: "≥",
+        aliases: &["≥", ">="],
+    },
+];
+
+/// Finds the relation written with `symbol`
+pub fn find_relation(symbol: &str) -> Option<&'static Relation> {
+    RELATIONS.iter().find(|v| v.aliases.contains(&symbol))
+}
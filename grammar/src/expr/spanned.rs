@@ -0,0 +1,152 @@
+use super::{
+    constantexpr::{Constant, ConstantExpr},
+    literal::Literal,
+    variable::Variable,
+    Expr,
+};
+use chumsky::span::SimpleSpan;
+
+/// A node of a parsed statement, along with the byte range of the input it was parsed from.
+/// Brackets around a node are part of its span, but whitespace is not.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: SimpleSpan,
+}
+
+/// An [Expr] whose parts all carry their spans
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SpannedExpr {
+    Literal(Literal),
+    Variable(Variable),
+    Tautology,
+    Contradiction,
+    Predicate {
+        pred: Literal,
+        args: Vec<Spanned<SpannedConstantExpr>>,
+    },
+    Not(Box<Spanned<SpannedExpr>>),
+    And(Box<Spanned<SpannedExpr>>, Box<Spanned<SpannedExpr>>),
+    Or(Box<Spanned<SpannedExpr>>, Box<Spanned<SpannedExpr>>),
+    Xor(Box<Spanned<SpannedExpr>>, Box<Spanned<SpannedExpr>>),
+    Conditional(Box<Spanned<SpannedExpr>>, Box<Spanned<SpannedExpr>>),
+    Biconditional(Box<Spanned<SpannedExpr>>, Box<Spanned<SpannedExpr>>),
+    Universal {
+        iter: Variable,
+        expr: Box<Spanned<SpannedExpr>>,
+    },
+    Existential {
+        iter: Variable,
+        expr: Box<Spanned<SpannedExpr>>,
+    },
+    Equals(Spanned<SpannedConstantExpr>, Spanned<SpannedConstantExpr>),
+    ConstantValue(Spanned<SpannedConstantExpr>),
+    UnknownOperator {
+        left: Box<Spanned<SpannedExpr>>,
+        operator: String,
+        right: Box<Spanned<SpannedExpr>>,
+    },
+    Invalid,
+}
+
+/// A [ConstantExpr] whose parts all carry their spans
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SpannedConstantExpr {
+    Constant(Constant),
+    Variable(Variable),
+    Number(isize),
+    Function {
+        func: Constant,
+        args: Vec<Spanned<SpannedConstantExpr>>,
+    },
+    Operator(
+        String,
+        Box<Spanned<SpannedConstantExpr>>,
+        Box<Spanned<SpannedConstantExpr>>,
+    ),
+}
+
+impl Spanned<SpannedExpr> {
+    /// Removes the spans, giving the [Expr] that was parsed
+    pub fn strip(&self) -> Expr {
+        let rec = |v: &Spanned<SpannedExpr>| Box::new(v.strip());
+
+        match &self.node {
+            SpannedExpr::Literal(v) => Expr::Literal(v.clone()),
+            SpannedExpr::Variable(v) => Expr::Variable(v.clone()),
+            SpannedExpr::Tautology => Expr::Tautology,
+            SpannedExpr::Contradiction => Expr::Contradiction,
+            SpannedExpr::Predicate { pred, args } => Expr::Predicate {
+                pred: pred.clone(),
+                args: args.iter().map(|v| v.strip()).collect(),
+            },
+            SpannedExpr::Not(v) => Expr::Not(rec(v)),
+            SpannedExpr::And(l, r) => Expr::And(rec(l), rec(r)),
+            SpannedExpr::Or(l, r) => Expr::Or(rec(l), rec(r)),
+            SpannedExpr::Xor(l, r) => Expr::Xor(rec(l), rec(r)),
+            SpannedExpr::Conditional(l, r) => Expr::Conditional(rec(l), rec(r)),
+            SpannedExpr::Biconditional(l, r) => Expr::Biconditional(rec(l), rec(r)),
+            SpannedExpr::Universal { iter, expr } => Expr::Universal {
+                iter: iter.clone(),
+                expr: rec(expr),
+            },
+            SpannedExpr::Existential { iter, expr } => Expr::Existential {
+                iter: iter.clone(),
+                expr: rec(expr),
+            },
+            SpannedExpr::Equals(l, r) => Expr::Equals(l.strip(), r.strip()),
+            SpannedExpr::ConstantValue(v) => Expr::ConstantValue(v.strip()),
+            SpannedExpr::UnknownOperator {
+                left,
+                operator,
+                right,
+            } => Expr::UnknownOperator {
+                left: rec(left),
+                operator: operator.clone(),
+                right: rec(right),
+            },
+            SpannedExpr::Invalid => Expr::Invalid,
+        }
+    }
+
+    /// Finds the span of the first part of this statement that is `target`, searching outer parts
+    /// before the parts inside of them and left parts before right ones
+    pub fn span_of(&self, target: &Expr) -> Option<SimpleSpan> {
+        if self.strip() == *target {
+            return Some(self.span);
+        }
+
+        match &self.node {
+            SpannedExpr::Not(v)
+            | SpannedExpr::Universal { expr: v, .. }
+            | SpannedExpr::Existential { expr: v, .. } => v.span_of(target),
+            SpannedExpr::And(l, r)
+            | SpannedExpr::Or(l, r)
+            | SpannedExpr::Xor(l, r)
+            | SpannedExpr::Conditional(l, r)
+            | SpannedExpr::Biconditional(l, r)
+            | SpannedExpr::UnknownOperator {
+                left: l, right: r, ..
+            } => l.span_of(target).or_else(|| r.span_of(target)),
+            _ => None,
+        }
+    }
+}
+
+impl Spanned<SpannedConstantExpr> {
+    /// Removes the spans, giving the [ConstantExpr] that was parsed
+    pub fn strip(&self) -> ConstantExpr {
+        match &self.node {
+            SpannedConstantExpr::Constant(v) => ConstantExpr::Constant(v.clone()),
+            SpannedConstantExpr::Variable(v) => ConstantExpr::Variable(v.clone()),
+            SpannedConstantExpr::Number(v) => ConstantExpr::Number(*v),
+            SpannedConstantExpr::Function { func, args } => ConstantExpr::Function {
+                func: func.clone(),
+                args: args.iter().map(|v| v.strip()).collect(),
+            },
+            SpannedConstantExpr::Operator(op, l, r) => {
+                ConstantExpr::Operator(op.clone(), Box::new(l.strip()), Box::new(r.strip()))
+            }
+        }
+    }
+}
@@ -1,5 +1,8 @@
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Variable {
     pub name: String,
-    pub id: String,
+    /// How many quantifiers lie between this variable and the quantifier that binds it
+    /// (its de Bruijn index), so the same statement always parses to the same [Variable]s.
+    /// The variable a quantifier introduces has index 0, and unbound variables have [None].
+    pub index: Option<usize>,
 }
@@ -1,6 +1,9 @@
+pub mod diagnostic;
+pub mod dialect;
 pub mod error;
 pub mod expr;
 mod parser;
+pub mod printer;
 
 pub use chumsky::Parser;
 
@@ -8,23 +11,50 @@ use chumsky::{
     cache::{Cache, Cached},
     Boxed,
 };
-use expr::Expr;
+use dialect::Dialect;
+use expr::{
+    spanned::{Spanned, SpannedExpr},
+    Expr,
+};
 use parser::{parser, Extras, Input};
 use std::cell::LazyCell;
 
 pub type YggdrasilGrammarParserType<'a, 'b, I = Input<'a>> = Boxed<'a, 'b, I, Expr, Extras<'a>>;
+pub type YggdrasilGrammarSpannedParserType<'a, 'b, I = Input<'a>> =
+    Boxed<'a, 'b, I, Spanned<SpannedExpr>, Extras<'a>>;
 
+/// Makes the parser for a [Dialect]. Use it with [Cache::new] for a dialect other than the
+/// default one [PARSER] uses.
 #[derive(Default)]
-pub struct YggdrasilGrammarParserCache;
+pub struct YggdrasilGrammarParserCache {
+    pub dialect: Dialect,
+}
 
 impl Cached for YggdrasilGrammarParserCache {
     type Parser<'src> = YggdrasilGrammarParserType<'src, 'src>;
 
     fn make_parser<'src>(self) -> Self::Parser<'src> {
-        parser().boxed()
+        parser(self.dialect).map(|v| v.strip()).boxed()
+    }
+}
+
+/// Makes a parser like [YggdrasilGrammarParserCache], but which keeps the span of every part of
+/// the statement
+#[derive(Default)]
+pub struct YggdrasilGrammarSpannedParserCache {
+    pub dialect: Dialect,
+}
+
+impl Cached for YggdrasilGrammarSpannedParserCache {
+    type Parser<'src> = YggdrasilGrammarSpannedParserType<'src, 'src>;
+
+    fn make_parser<'src>(self) -> Self::Parser<'src> {
+        parser(self.dialect).boxed()
     }
 }
 
 thread_local! {
     pub static PARSER: LazyCell<Cache<YggdrasilGrammarParserCache>> = LazyCell::new(Cache::default);
+    pub static SPANNED_PARSER: LazyCell<Cache<YggdrasilGrammarSpannedParserCache>> =
+        LazyCell::new(Cache::default);
 }
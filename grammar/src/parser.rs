@@ -1,38 +1,178 @@
 use crate::{
+    diagnostic::{DiagnosticCode, Edit, Suggestion},
+    dialect::{ChainNesting, Dialect},
     error::YggError,
     expr::{
-        constantexpr::{Constant, ConstantExpr},
+        connective::{
+            suggest_connective, Connective, AND, BICONDITIONAL, CONDITIONAL, CONNECTIVES, OR, XOR,
+        },
+        constantexpr::Constant,
         literal::Literal,
+        operator::{Fixity, TermOperator, TERM_OPERATORS},
+        relation::{find_relation, RELATIONS},
+        spanned::{Spanned, SpannedConstantExpr, SpannedExpr},
         variable::Variable,
-        Expr,
     },
 };
 use chumsky::{
     extra::Full,
-    prelude::{choice, just, map_ctx, recursive, regex, Rich},
-    span::Span,
+    prelude::{any, choice, just, map_ctx, none_of, one_of, recursive, regex},
+    span::{SimpleSpan, Span},
     Boxed, IterParser, Parser,
 };
-use std::{collections::HashMap, fmt::Debug};
+use std::fmt::Debug;
 
 pub(crate) type Input<'a> = &'a str;
-pub(crate) type ContextType = HashMap<String, Variable>;
+/// Names of the variables introduced by enclosing quantifiers, innermost last
+pub(crate) type ContextType = Vec<String>;
 pub(crate) type Extras<'a> = Full<YggError<'a, char>, (), ContextType>;
 
-fn grouping<'a, E, A: Parser<'a, Input<'a>, E, Extras<'a>> + Clone>(
+type SExpr = Spanned<SpannedExpr>;
+type STerm = Spanned<SpannedConstantExpr>;
+// an operator after the first in a chain, which of the operators it is, and the operand after it
+type ChainedOperand<'a, E> = (((usize, &'a str), SimpleSpan), Option<Spanned<E>>);
+
+// attaches the span of what `parser` parsed to its output
+fn spanned<'a, T, P: Parser<'a, Input<'a>, T, Extras<'a>> + Clone>(
+    parser: P,
+) -> impl Parser<'a, Input<'a>, Spanned<T>, Extras<'a>> + Clone {
+    parser.map_with(|node, e| Spanned {
+        node,
+        span: e.span(),
+    })
+}
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+// the span of a grouped node includes its brackets
+fn grouping<'a, E, A: Parser<'a, Input<'a>, Spanned<E>, Extras<'a>> + Clone>(
     atom: A,
-) -> impl Parser<'a, Input<'a>, E, Extras<'a>> + Clone {
+) -> impl Parser<'a, Input<'a>, Spanned<E>, Extras<'a>> + Clone {
     choice(
-        [("(", ")"), ("[", "]"), ("{", "}")]
+        BRACKETS
             .into_iter()
             .map(|(open, close)| {
                 atom.clone()
-                    .delimited_by(just(open).padded(), just(close).padded())
+                    .padded()
+                    .delimited_by(just(open), just(close))
+                    .map_with(|v, e| Spanned {
+                        span: e.span(),
+                        ..v
+                    })
             })
             .collect::<Vec<_>>(),
     )
 }
 
+// like grouping, but problems inside the brackets are reported without failing the statement:
+// input after the statement is skipped, a missing closing bracket is reported, and brackets with
+// no statement at the start become a hole
+fn statement_grouping<'a, A: Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone>(
+    expr: A,
+) -> impl Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone {
+    choice(
+        BRACKETS
+            .into_iter()
+            .map(|(open, close)| {
+                let stray = none_of(")]}")
+                    .repeated()
+                    .at_least(1)
+                    .to_slice()
+                    .map_with(|v: &str, e| (v, e.span()));
+
+                just(open)
+                    .to_span()
+                    .then(expr.clone().padded().or_not())
+                    .then(stray.or_not())
+                    .then(just(close).or_not())
+                    .validate(move |(((open_span, v), stray), closed), e, emitter| {
+                        let span: SimpleSpan = e.span();
+                        let v = match v {
+                            Some(v) => {
+                                if let Some((stray, span)) = stray {
+                                    emitter.emit(unexpected(stray, span));
+                                }
+                                v
+                            }
+                            None => {
+                                emitter.emit(YggError::custom(
+                                    DiagnosticCode::Syntax,
+                                    vec![span],
+                                    "Expected a statement",
+                                ));
+                                Spanned {
+                                    node: SpannedExpr::Invalid,
+                                    span,
+                                }
+                            }
+                        };
+
+                        if closed.is_none() {
+                            emitter.emit(
+                                YggError::custom(
+                                    DiagnosticCode::UnclosedBracket,
+                                    vec![open_span],
+                                    format!("Unclosed bracket {}", open),
+                                )
+                                .with_suggestion(Suggestion {
+                                    message: format!("close it with {}", close),
+                                    edits: vec![Edit {
+                                        span: span.end..span.end,
+                                        replacement: close.to_string(),
+                                    }],
+                                }),
+                            );
+                        }
+
+                        Spanned { span, ..v }
+                    })
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn unknown_operator_error<'a>(op: &str, op_span: SimpleSpan) -> YggError<'a, char> {
+    let error = YggError::custom(
+        DiagnosticCode::UnknownOperator,
+        vec![op_span],
+        format!("Unknown operator {}", op),
+    );
+    match suggest_connective(op) {
+        Some(connective) => error.with_suggestion(Suggestion {
+            message: format!("use the {} {}", connective.name, connective.symbol),
+            edits: vec![Edit {
+                span: op_span.into_range(),
+                replacement: connective.symbol.to_string(),
+            }],
+        }),
+        None => error,
+    }
+}
+
+fn missing_operand<'a>(op: &str, op_span: SimpleSpan) -> YggError<'a, char> {
+    YggError::custom(
+        DiagnosticCode::MissingOperand,
+        vec![op_span],
+        format!("Missing operand after {}", op),
+    )
+}
+
+// input that isn't part of the statement
+fn unexpected<'a>(stray: &str, span: SimpleSpan) -> YggError<'a, char> {
+    YggError::custom(
+        DiagnosticCode::UnexpectedInput,
+        vec![span],
+        format!("Unexpected {}", stray.trim()),
+    )
+    .with_suggestion(Suggestion {
+        message: "remove it".to_string(),
+        edits: vec![Edit {
+            span: span.into_range(),
+            replacement: String::new(),
+        }],
+    })
+}
+
 fn literal<'a>() -> impl Parser<'a, Input<'a>, Literal, Extras<'a>> + Clone {
     regex("[A-Z][a-zA-Z0-9]*").map(|v: &str| Literal(v.to_string()))
 }
@@ -40,199 +180,371 @@ fn literal<'a>() -> impl Parser<'a, Input<'a>, Literal, Extras<'a>> + Clone {
 fn variable<'a>(new: bool) -> impl Parser<'a, Input<'a>, Variable, Extras<'a>> + Clone {
     regex("[t-z][a-zA-Z0-9]*").validate(move |v: &str, e, emitter| {
         let ctx: &ContextType = e.ctx();
-        let found_var = ctx.get(v).cloned();
         if new {
             Variable {
                 name: v.to_string(),
-                id: nanoid::nanoid!(5),
+                index: Some(0),
             }
         } else {
-            match found_var {
-                Some(v) => v,
-                None => {
-                    emitter.emit(YggError::custom(
+            let index = ctx.iter().rev().position(|name| name == v);
+            if index.is_none() {
+                emitter.emit(
+                    YggError::custom(
+                        DiagnosticCode::UnboundVariable,
                         vec![e.span()],
                         "This variable does not exist",
-                    ));
-                    Variable {
-                        name: v.to_string(),
-                        id: "**invalid**".to_string(),
-                    }
-                }
+                    )
+                    .with_note("variables can only be used inside a quantifier that introduces them, like ∀x"),
+                );
+            }
+
+            Variable {
+                name: v.to_string(),
+                index,
             }
         }
     })
 }
 
-fn tautology<'a>() -> impl Parser<'a, Input<'a>, Expr, Extras<'a>> + Clone {
-    regex("⊤|1").to(Expr::Tautology)
+fn tautology<'a>() -> impl Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone {
+    spanned(regex("⊤|1").to(SpannedExpr::Tautology))
 }
 
-fn contradiction<'a>() -> impl Parser<'a, Input<'a>, Expr, Extras<'a>> + Clone {
-    regex("⊥|0").to(Expr::Contradiction)
+fn contradiction<'a>() -> impl Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone {
+    spanned(regex("⊥|0").to(SpannedExpr::Contradiction))
 }
 
-fn predicate<'a>() -> impl Parser<'a, Input<'a>, Expr, Extras<'a>> + Clone {
-    literal()
-        .then(
-            constant_expr()
-                .separated_by(just(","))
-                .at_least(1)
-                .collect::<Vec<_>>()
-                .delimited_by(just("("), just(")")),
-        )
-        .map(|(pred, args)| Expr::Predicate { pred, args })
+fn predicate<'a>() -> impl Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone {
+    spanned(
+        literal()
+            .then(
+                constant_expr()
+                    .separated_by(just(","))
+                    .at_least(1)
+                    .collect::<Vec<_>>()
+                    .delimited_by(just("("), just(")")),
+            )
+            .map(|(pred, args)| SpannedExpr::Predicate { pred, args }),
+    )
 }
 
 fn constant<'a>() -> impl Parser<'a, Input<'a>, Constant, Extras<'a>> + Clone {
     regex("[a-s][a-zA-Z0-9]*").map(|v: &str| Constant(v.to_string()))
 }
 
-fn function<'a, T: Parser<'a, Input<'a>, ConstantExpr, Extras<'a>> + Clone>(
+fn function<'a, T: Parser<'a, Input<'a>, STerm, Extras<'a>> + Clone>(
     atom: T,
-) -> impl Parser<'a, Input<'a>, ConstantExpr, Extras<'a>> + Clone {
-    constant()
-        .then(
-            atom.padded()
-                .separated_by(just(","))
-                .at_least(1)
-                .collect::<Vec<_>>()
-                .delimited_by(just("("), just(")")),
-        )
-        .map(|(func, args)| ConstantExpr::Function { func, args })
+) -> impl Parser<'a, Input<'a>, STerm, Extras<'a>> + Clone {
+    spanned(
+        constant()
+            .then(
+                atom.padded()
+                    .separated_by(just(","))
+                    .at_least(1)
+                    .collect::<Vec<_>>()
+                    .delimited_by(just("("), just(")")),
+            )
+            .map(|(func, args)| SpannedConstantExpr::Function { func, args }),
+    )
 }
 
-type InfixOpMap<T = Expr, R = T> = fn(Box<T>, Box<T>) -> R;
+type InfixOpMap<T = SpannedExpr> = fn(Box<Spanned<T>>, Box<Spanned<T>>) -> T;
+type UnknownOpMap<T = SpannedExpr> = fn(Box<Spanned<T>>, String, Box<Spanned<T>>) -> T;
+
+// what follows the first operand of an infix operator
+enum Tail<'a, E> {
+    Known(Vec<ChainedOperand<'a, E>>),
+    Unknown((&'a str, SimpleSpan), Spanned<E>),
+}
 
 // used for infix operators like & and |, so that they all have the same precedence without associativity
+// operators marked as chainable can be repeated if `chains` is set, and are grouped in that direction
+// the first operand is only parsed once, and is the output on its own if no operator follows it
+// if `unknown` is set, an operator that isn't one of these is reported along with the connective
+// that was probably meant, instead of ending the statement
 fn infix_op_set<
     'a,
-    E: Clone + Debug,
-    R: Clone + 'a,
-    T: Parser<'a, Input<'a>, E, Extras<'a>> + Clone,
+    E: Clone + Debug + 'a,
+    T: Parser<'a, Input<'a>, Spanned<E>, Extras<'a>> + Clone,
     I: IntoIterator<
-            Item = (
-                Boxed<'a, 'a, Input<'a>, Input<'a>, Extras<'a>>,
-                InfixOpMap<E, R>,
-            ),
-        > + Clone,
+        Item = (
+            Boxed<'a, 'a, Input<'a>, Input<'a>, Extras<'a>>,
+            InfixOpMap<E>,
+            bool,
+        ),
+    >,
 >(
     atom: T,
     things: I,
-    fallback: R,
-) -> impl Parser<'a, Input<'a>, R, Extras<'a>> + Clone {
-    let any_op = choice(
-        things
-            .clone()
-            .into_iter()
-            .map(|(v, _)| v)
+    chains: Option<ChainNesting>,
+    fallback: E,
+    unknown: Option<UnknownOpMap<E>>,
+) -> impl Parser<'a, Input<'a>, Spanned<E>, Extras<'a>> + Clone {
+    let (ops, maps): (Vec<_>, Vec<_>) = things
+        .into_iter()
+        .map(|(op, into, chainable)| (op, (into, chainable)))
+        .unzip();
+
+    // a missing operand is reported and becomes a hole, so the rest of the statement can still be
+    // parsed
+    let operand = atom.clone().or_not();
+
+    // every operator after the first operand, along with the operand after it
+    // the operators this function was called with are not associative, so extra usages of them
+    // are captured in order to be reported
+    let known = choice(
+        ops.into_iter()
+            .enumerate()
+            .map(|(i, v)| v.map(move |op| (i, op)))
             .collect::<Vec<_>>(),
-    );
+    )
+    .map_with(|(i, op), e| ((i, op), e.span()))
+    .padded()
+    .then(operand)
+    .padded()
+    .repeated()
+    .at_least(1)
+    .collect::<Vec<_>>()
+    .map(Tail::Known);
 
-    choice(
-        things
-            .into_iter()
-            .map({
-                |(op, into)| {
-                    atom.clone()
-                        .then((op.clone().to_span()).padded())
-                        .then(atom.clone())
-                        // attempt to capture extra usages of the operator and report them
-                        // the operators this function was called with are not associative
-                        .then(
-                            (any_op.clone().to_span().then_ignore(atom.clone()))
-                                .padded()
-                                .repeated()
-                                .collect::<Vec<_>>(),
+    let unknown_tail = unknown_operator()
+        .filter(move |_| unknown.is_some())
+        .map_with(|op, e| (op, e.span()))
+        .padded()
+        .then(atom.clone())
+        .map(|(op, right)| Tail::Unknown(op, right));
+
+    atom.then(choice((known, unknown_tail)).or_not()).validate(
+        move |(a, tail): (Spanned<E>, Option<Tail<E>>), _, emitter| {
+            let operators = match tail {
+                None => return a,
+                Some(Tail::Unknown((op, span), b)) => {
+                    emitter.emit(unknown_operator_error(op, span));
+                    return Spanned {
+                        span: a.span.union(b.span),
+                        node: unknown.unwrap()(Box::new(a), op.to_string(), Box::new(b)),
+                    };
+                }
+                Some(Tail::Known(operators)) => operators,
+            };
+
+            let mut hole = |operand: Option<Spanned<E>>, op: &str, span: SimpleSpan| {
+                operand.unwrap_or_else(|| {
+                    emitter.emit(missing_operand(op, span));
+                    Spanned {
+                        node: fallback.clone(),
+                        span: (span.end..span.end).into(),
+                    }
+                })
+            };
+            let mut operators = operators
+                .into_iter()
+                .map(|(((i, op), span), v)| ((i, span), hole(v, op, span)));
+            let ((i, op_span), b) = operators.next().unwrap();
+            let extra = operators.collect::<Vec<_>>();
+            let (into, chainable) = maps[i];
+
+            let nesting =
+                chains.filter(|_| chainable && extra.iter().all(|((v, _), _)| *v == i));
+
+            let join = |l: Spanned<E>, r: Spanned<E>| Spanned {
+                span: l.span.union(r.span),
+                node: into(Box::new(l), Box::new(r)),
+            };
+
+            match nesting {
+                _ if extra.is_empty() => join(a, b),
+                Some(nesting) => {
+                    let operands = [a, b].into_iter().chain(extra.into_iter().map(|(_, v)| v));
+
+                    match nesting {
+                        ChainNesting::Left => operands.reduce(join),
+                        ChainNesting::Right => operands.rev().reduce(|r, l| join(l, r)),
+                    }
+                    .unwrap()
+                }
+                None => {
+                    let span = extra.last().map_or(b.span, |(_, v)| v.span);
+                    let grouped = Suggestion {
+                        message: "group the first two operands".to_string(),
+                        edits: vec![
+                            Edit {
+                                span: a.span.start..a.span.start,
+                                replacement: "(".to_string(),
+                            },
+                            Edit {
+                                span: b.span.end..b.span.end,
+                                replacement: ")".to_string(),
+                            },
+                        ],
+                    };
+                    emitter.emit(
+                        YggError::custom(
+                            DiagnosticCode::NotAssociative,
+                            [op_span]
+                                .into_iter()
+                                .chain(extra.into_iter().map(|((_, span), _)| span))
+                                .collect(),
+                            "These operators are not associative; use parentheses to indicate order of operation",
                         )
-                        .validate({
-                            let fallback = fallback.clone();
-                            move |(((a, op_span), b), invalid_input), _, emitter| {
-                                if invalid_input.is_empty() {
-                                    into(Box::new(a.clone()), Box::new(b))
-                                } else {
-                                    emitter.emit(
-                                        YggError::custom(
-                                            invalid_input.into_iter().chain([op_span].into_iter()).collect(),
-                                            "These operators are not associative; use parentheses to indicate order of operation"
-                                        )
-                                    );
-
-                                    fallback.clone()
-                                }
-                            }
-                        })
+                        .with_suggestion(grouped),
+                    );
+
+                    Spanned {
+                        node: fallback.clone(),
+                        span: a.span.union(span),
+                    }
                 }
-            })
-            .collect::<Vec<_>>(),
+            }
+        },
     )
 }
 
 // used for universal and existential expressions
 fn quantifier<
     'a,
-    T: Parser<'a, Input<'a>, Expr, Extras<'a>> + Clone,
-    M: Fn(Variable, Box<Expr>) -> Expr + Clone,
+    T: Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone,
+    M: Fn(Variable, Box<SExpr>) -> SpannedExpr + Clone,
 >(
     atom: T,
     sym: Input<'a>,
     map: M,
-) -> impl Parser<'a, Input<'a>, Expr, Extras<'a>> + Clone {
+) -> impl Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone {
     regex(sym)
-        .ignore_then(variable(true).padded())
-        .map_with(|v, e| {
+        .to_span()
+        .then(variable(true).padded())
+        .map_with(|(span, v), e| {
             let mut new_ctx = e.ctx().clone();
-            new_ctx.insert(v.name.clone(), v.clone());
-            (new_ctx, v)
+            new_ctx.push(v.name.clone());
+            (new_ctx, (span, v))
         })
         .then_with_ctx(map_ctx(|(ctx, _): &(ContextType, _)| ctx.clone(), atom))
-        .map(move |((_, var), atom)| map(var, Box::new(atom)))
+        .map(move |((_, (span, var)), atom)| Spanned {
+            span: span.union(atom.span),
+            node: map(var, Box::new(atom)),
+        })
+}
+
+// any of the symbols a connective can be written with; one written twice, like `&&`, is left for
+// unknown_operator so it can be suggested
+fn connective<'a>(connective: Connective) -> Boxed<'a, 'a, Input<'a>, Input<'a>, Extras<'a>> {
+    choice(
+        connective
+            .aliases
+            .iter()
+            .map(|v| just(*v).then_ignore(just(*v).not()))
+            .collect::<Vec<_>>(),
+    )
+    .to_slice()
+    .boxed()
+}
+
+// anything that could be meant as an operator between statements, so unknown ones can be reported
+fn unknown_operator<'a>() -> impl Parser<'a, Input<'a>, &'a str, Extras<'a>> + Clone {
+    regex(r"[^\sA-Za-z0-9()\[\]{},¬∀∃⊤⊥]+|v|and|or|xor|iff|implies")
 }
 
-fn constant_expr_operator<'a>() -> impl Parser<'a, Input<'a>, &'a str, Extras<'a>> + Clone {
-    regex(r"[^\w\s⊤⊥\(\)\[\]\{\}¬→↔∀@∃]{1,2}").padded()
+// anything that looks like a relation, so unknown ones can be reported
+fn relation_operator<'a>() -> impl Parser<'a, Input<'a>, &'a str, Extras<'a>> + Clone {
+    regex(r"[=<>≤≥≠!]{1,2}")
 }
 
-fn constant_expr_atom<'a, T: Parser<'a, Input<'a>, ConstantExpr, Extras<'a>> + Clone>(
+fn constant_expr_atom<'a, T: Parser<'a, Input<'a>, STerm, Extras<'a>> + Clone>(
     const_expr: T,
-) -> impl Parser<'a, Input<'a>, ConstantExpr, Extras<'a>> + Clone {
+) -> impl Parser<'a, Input<'a>, STerm, Extras<'a>> + Clone {
     choice((
         grouping(const_expr.clone()),
         function(const_expr.clone()),
-        constant().map(ConstantExpr::Constant),
-        variable(false).map(ConstantExpr::Variable),
+        spanned(constant().map(SpannedConstantExpr::Constant)),
+        spanned(variable(false).map(SpannedConstantExpr::Variable)),
         regex(r"[0-9]+").try_map(|digits: &str, span| {
-            Ok(ConstantExpr::Number(digits.parse().map_err(|e| {
-                YggError::custom(vec![span], format!("Could not parse number: {}", e))
-            })?))
+            Ok(Spanned {
+                node: SpannedConstantExpr::Number(digits.parse().map_err(|e| {
+                    YggError::custom(
+                        DiagnosticCode::InvalidNumber,
+                        vec![span],
+                        format!("Could not parse number: {}", e),
+                    )
+                })?),
+                span,
+            })
         }),
     ))
 }
 
-fn constant_expr<'a>() -> impl Parser<'a, Input<'a>, ConstantExpr, Extras<'a>> + Clone {
-    recursive(|const_expr| {
-        let atom = constant_expr_atom(const_expr);
+// builds terms out of atoms with the operators in `operators`, one precedence level at a time
+fn term_operators<'a, T: Parser<'a, Input<'a>, STerm, Extras<'a>> + Clone + 'a>(
+    atom: T,
+    operators: &'static [TermOperator],
+) -> Boxed<'a, 'a, Input<'a>, STerm, Extras<'a>> {
+    let mut levels = operators.iter().map(|v| v.precedence).collect::<Vec<_>>();
+    levels.sort_unstable_by(|a, b| b.cmp(a));
+    levels.dedup();
 
-        choice((
-            atom.clone()
-                .then(constant_expr_operator())
-                .then(atom.clone())
-                .map(move |((a, op), b): ((_, &str), _)| {
-                    ConstantExpr::Operator(op.to_string(), Box::new(a), Box::new(b))
-                }),
-            atom,
-        ))
+    levels.into_iter().fold(atom.boxed(), |operand, level| {
+        let level_ops = operators.iter().filter(|v| v.precedence == level);
+        let symbol = choice(
+            level_ops
+                .clone()
+                .flat_map(|op| op.aliases.iter().map(move |alias| just(*alias).to(op)))
+                .collect::<Vec<_>>(),
+        )
+        .map_with(|op, e| (op, e.span()))
+        .padded();
+
+        let infix = |(op, _): (&TermOperator, _), left: STerm, right: STerm| Spanned {
+            span: left.span.union(right.span),
+            node: SpannedConstantExpr::Operator(
+                op.symbol.to_string(),
+                Box::new(left),
+                Box::new(right),
+            ),
+        };
+
+        match level_ops.clone().next().map(|v| v.fixity) {
+            Some(Fixity::InfixLeft) => operand
+                .clone()
+                .foldl(symbol.then(operand).repeated(), move |left, (op, right)| {
+                    infix(op, left, right)
+                })
+                .boxed(),
+            Some(Fixity::InfixRight) => recursive(|this| {
+                operand
+                    .then(symbol.then(this).or_not())
+                    .map(move |(left, right)| match right {
+                        Some((op, right)) => infix(op, left, right),
+                        None => left,
+                    })
+            })
+            .boxed(),
+            Some(Fixity::Postfix(_)) => operand
+                .foldl(symbol.repeated(), |term, (op, span)| match op.fixity {
+                    Fixity::Postfix(func) => Spanned {
+                        span: term.span.union(span),
+                        node: SpannedConstantExpr::Function {
+                            func: Constant(func.to_string()),
+                            args: vec![term],
+                        },
+                    },
+                    _ => term,
+                })
+                .boxed(),
+            None => operand,
+        }
     })
-    .padded()
 }
 
-pub fn parser<'a>() -> impl Parser<'a, Input<'a>, Expr, Extras<'a>> + Clone {
-    recursive(|expr| {
+fn constant_expr<'a>() -> impl Parser<'a, Input<'a>, STerm, Extras<'a>> + Clone {
+    recursive(|const_expr| term_operators(constant_expr_atom(const_expr), TERM_OPERATORS)).padded()
+}
+
+pub fn parser<'a>(dialect: Dialect) -> impl Parser<'a, Input<'a>, SExpr, Extras<'a>> + Clone {
+    let statement = recursive(|expr| {
         // expr parsers
         let atom = choice((
-            grouping(expr),
+            statement_grouping(expr),
             predicate(),
-            literal().map(Expr::Literal),
+            spanned(literal().map(SpannedExpr::Literal)),
             contradiction(),
             tautology(),
         ))
@@ -243,78 +555,171 @@ pub fn parser<'a>() -> impl Parser<'a, Input<'a>, Expr, Extras<'a>> + Clone {
         let atom = recursive(|outer| {
             choice((
                 quantifier(choice((atom.clone(), outer.clone())), "∀|@", |v, e| {
-                    Expr::Universal { iter: v, expr: e }
+                    SpannedExpr::Universal { iter: v, expr: e }
                 }),
                 quantifier(choice((atom.clone(), outer.clone())), "∃|/", |v, e| {
-                    Expr::Existential { iter: v, expr: e }
+                    SpannedExpr::Existential { iter: v, expr: e }
                 }),
                 atom,
             ))
             .padded()
         });
 
-        // expression with only constants
+        // relations between terms
         let atom = choice((
-            // special case for inequality == not equal
-            constant_expr_atom(constant_expr())
-                .then_ignore(regex("!=|≠").padded())
-                .then(constant_expr_atom(constant_expr()))
-                .map(|(a, b)| {
-                    Expr::Not(Box::new(Expr::ConstantValue(ConstantExpr::Operator(
-                        "=".to_string(),
-                        Box::new(a),
-                        Box::new(b),
-                    ))))
-                }),
-            // normal  case
-            constant_expr_atom(constant_expr())
-                .then(constant_expr_operator())
-                .then(constant_expr_atom(constant_expr()))
-                .map(|((a, op), b)| {
-                    Expr::ConstantValue(ConstantExpr::Operator(
-                        op.to_string(),
-                        Box::new(a),
-                        Box::new(b),
-                    ))
+            constant_expr()
+                .then(
+                    relation_operator()
+                        .map_with(|op, e| (op, e.span()))
+                        .padded(),
+                )
+                .then(constant_expr())
+                .validate(|((a, (op, op_span)), b): ((STerm, _), STerm), _, emitter| {
+                    let span = a.span.union(b.span);
+                    let node = match op {
+                        "=" => SpannedExpr::Equals(a, b),
+                        "!=" | "≠" => SpannedExpr::Not(Box::new(Spanned {
+                            node: SpannedExpr::Equals(a, b),
+                            span,
+                        })),
+                        op => match find_relation(op) {
+                            Some(relation) => SpannedExpr::ConstantValue(Spanned {
+                                node: SpannedConstantExpr::Operator(
+                                    relation.symbol.to_string(),
+                                    Box::new(a),
+                                    Box::new(b),
+                                ),
+                                span,
+                            }),
+                            None => {
+                                let known = ["=", "≠"]
+                                    .into_iter()
+                                    .chain(RELATIONS.iter().map(|v| v.symbol))
+                                    .collect::<Vec<_>>();
+                                emitter.emit(
+                                    YggError::custom(
+                                        DiagnosticCode::UnknownRelation,
+                                        vec![op_span],
+                                        format!("Unknown relation {}", op),
+                                    )
+                                    .with_note(format!("the relations are {}", known.join(" "))),
+                                );
+                                SpannedExpr::Invalid
+                            }
+                        },
+                    };
+
+                    Spanned { node, span }
                 }),
             atom,
         ));
 
         // not operator
-        let atom = regex("¬|~|!").or_not().then(atom).map(|(n, v)| match n {
-            Some(_) => Expr::Not(Box::new(v)),
-            None => v,
-        });
+        let atom = regex("¬|~|!")
+            .to_span()
+            .or_not()
+            .then(atom)
+            .map(|(n, v)| match n {
+                Some(span) => Spanned {
+                    span: span.union(v.span),
+                    node: SpannedExpr::Not(Box::new(v)),
+                },
+                None => v,
+            });
 
         // and, or, xor
-        let atom = choice((
-            infix_op_set(
-                atom.clone(),
-                vec![
-                    (regex(r"∧|\*|&").boxed(), Expr::And as InfixOpMap),
-                    (regex(r"∨|\+|\|").boxed(), Expr::Or),
-                    (regex(r"⊕|(!∨)|(!\+)|(!\|)").boxed(), Expr::Xor),
-                ],
-                Expr::Invalid,
-            ),
+        let atom = infix_op_set(
             atom,
-        ))
+            vec![
+                (connective(AND), SpannedExpr::And as InfixOpMap, true),
+                (connective(OR), SpannedExpr::Or, true),
+                (connective(XOR), SpannedExpr::Xor, false),
+            ],
+            dialect.chains,
+            SpannedExpr::Invalid,
+            None,
+        )
         .padded();
 
         // conditional and biconditional
-        let atom = choice((
-            infix_op_set(
-                atom.clone(),
-                vec![
-                    (regex("→|(->)").boxed(), Expr::Conditional as InfixOpMap),
-                    (regex(r"↔|(<->)").boxed(), Expr::Biconditional),
-                ],
-                Expr::Invalid,
-            ),
+        // operators that aren't connectives, like ones from other textbooks, are reported here
+        let atom = infix_op_set(
             atom,
-        ))
+            vec![
+                (
+                    connective(CONDITIONAL),
+                    SpannedExpr::Conditional as InfixOpMap,
+                    false,
+                ),
+                (connective(BICONDITIONAL), SpannedExpr::Biconditional, false),
+            ],
+            None,
+            SpannedExpr::Invalid,
+            Some(|left, operator, right| SpannedExpr::UnknownOperator {
+                left,
+                operator,
+                right,
+            }),
+        )
         .padded();
 
         atom
+    });
+
+    // input before the statement is skipped up to the next closing bracket or connective
+    let sync = choice((
+        one_of(")]}").ignored(),
+        choice(
+            CONNECTIVES
+                .iter()
+                .flat_map(|v| v.aliases)
+                .map(|v| just(*v).ignored())
+                .collect::<Vec<_>>(),
+        ),
+    ));
+
+    choice((
+        statement.clone(),
+        any()
+            .and_is(sync.clone().not())
+            .repeated()
+            .then(sync)
+            .to_slice()
+            .map_with(|v: &str, e| (v, e.span()))
+            .then(statement)
+            .validate(|((stray, span), expr), _, emitter| {
+                emitter.emit(unexpected(stray, span));
+                expr
+            }),
+        // and if nothing can be, the statement is a hole
+        any()
+            .repeated()
+            .map_with(|_, e| e.span())
+            .validate(|span, _, emitter| {
+                emitter.emit(YggError::custom(
+                    DiagnosticCode::Syntax,
+                    vec![span],
+                    "Expected a statement",
+                ));
+                Spanned {
+                    node: SpannedExpr::Invalid,
+                    span,
+                }
+            }),
+    ))
+    // anything left after the statement is reported, instead of failing the whole statement
+    .then(
+        any()
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .map_with(|v: &str, e| (v, e.span()))
+            .or_not(),
+    )
+    .validate(|(expr, stray), _, emitter| {
+        if let Some((stray, span)) = stray {
+            emitter.emit(unexpected(stray, span));
+        }
+        expr
     })
 }
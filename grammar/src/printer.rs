@@ -0,0 +1,312 @@
+use crate::expr::{
+    constantexpr::{Constant, ConstantExpr},
+    literal::Literal,
+    operator::{Fixity, TERM_OPERATORS},
+    relation::find_relation,
+    variable::Variable,
+    Expr,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// The symbols to write statements with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Style {
+    /// Logical symbols, like `∀x (P(x) → Q)`
+    #[default]
+    Unicode,
+
+    /// Symbols that can be typed on any keyboard, like `@x (P(x) -> Q)`
+    Ascii,
+
+    /// LaTeX math mode commands, like `\forall x\, (P(x) \to Q)`
+    Latex,
+}
+
+impl Style {
+    fn connective(&self, expr: &Expr) -> &'static str {
+        match (self, expr) {
+            (Self::Unicode, Expr::Not(_)) => "¬",
+            (Self::Unicode, Expr::And(..)) => "∧",
+            (Self::Unicode, Expr::Or(..)) => "∨",
+            (Self::Unicode, Expr::Xor(..)) => "⊕",
+            (Self::Unicode, Expr::Conditional(..)) => "→",
+            (Self::Unicode, Expr::Biconditional(..)) => "↔",
+            (Self::Unicode, Expr::Universal { .. }) => "∀",
+            (Self::Unicode, Expr::Existential { .. }) => "∃",
+            (Self::Unicode, Expr::Tautology) => "⊤",
+            (Self::Unicode, Expr::Contradiction) => "⊥",
+            (Self::Ascii, Expr::Not(_)) => "~",
+            (Self::Ascii, Expr::And(..)) => "&",
+            (Self::Ascii, Expr::Or(..)) => "|",
+            (Self::Ascii, Expr::Xor(..)) => "!|",
+            (Self::Ascii, Expr::Conditional(..)) => "->",
+            (Self::Ascii, Expr::Biconditional(..)) => "<->",
+            (Self::Ascii, Expr::Universal { .. }) => "@",
+            (Self::Ascii, Expr::Existential { .. }) => "/",
+            (Self::Ascii, Expr::Tautology) => "1",
+            (Self::Ascii, Expr::Contradiction) => "0",
+            (Self::Latex, Expr::Not(_)) => "\\neg ",
+            (Self::Latex, Expr::And(..)) => "\\land",
+            (Self::Latex, Expr::Or(..)) => "\\lor",
+            (Self::Latex, Expr::Xor(..)) => "\\oplus",
+            (Self::Latex, Expr::Conditional(..)) => "\\to",
+            (Self::Latex, Expr::Biconditional(..)) => "\\leftrightarrow",
+            (Self::Latex, Expr::Universal { .. }) => "\\forall ",
+            (Self::Latex, Expr::Existential { .. }) => "\\exists ",
+            (Self::Latex, Expr::Tautology) => "\\top",
+            (Self::Latex, Expr::Contradiction) => "\\bot",
+            _ => "?",
+        }
+    }
+
+    fn not_equals(&self) -> &'static str {
+        match self {
+            Self::Unicode => "≠",
+            Self::Ascii => "!=",
+            Self::Latex => "\\neq",
+        }
+    }
+
+    /// Picks how to write a relation or term operator, out of the symbols it can be written with
+    fn symbol<'a>(&self, symbol: &'a str, aliases: &[&'a str]) -> &'a str {
+        match self {
+            Self::Unicode => aliases
+                .iter()
+                .find(|v| !v.is_ascii())
+                .copied()
+                .unwrap_or(symbol),
+            Self::Ascii => aliases
+                .iter()
+                .find(|v| v.is_ascii())
+                .copied()
+                .unwrap_or(symbol),
+            Self::Latex => match symbol {
+                "≤" => "\\leq",
+                "≥" => "\\geq",
+                "*" => "\\times",
+                "/" => "\\div",
+                "%" => "\\bmod",
+                v => v,
+            },
+        }
+    }
+}
+
+/// Writes a statement out in `style`, with only the parentheses needed to parse it back into the
+/// same [Expr]
+pub fn print(expr: &Expr, style: Style) -> String {
+    let mut out = String::new();
+    write_expr(&mut out, expr, style);
+    out
+}
+
+/// Writes a term out in `style`, with only the parentheses needed to parse it back into the same
+/// [ConstantExpr]
+pub fn print_term(term: &ConstantExpr, style: Style) -> String {
+    let mut out = String::new();
+    write_term(&mut out, term, style);
+    out
+}
+
+/// How tightly a statement holds together when it is written out; the parts of a statement need
+/// parentheses when they don't bind more tightly than it
+fn binding(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Conditional(..) | Expr::Biconditional(..) | Expr::UnknownOperator { .. } => 0,
+        Expr::And(..) | Expr::Or(..) | Expr::Xor(..) => 1,
+        Expr::Not(inner) if !matches!(inner.as_ref(), Expr::Equals(..)) => 2,
+        _ => 3,
+    }
+}
+
+fn write_expr(out: &mut String, expr: &Expr, style: Style) {
+    let grouped = |out: &mut String, expr: &Expr, parens: bool| {
+        if parens {
+            out.push('(');
+            write_expr(out, expr, style);
+            out.push(')');
+        } else {
+            write_expr(out, expr, style);
+        }
+    };
+
+    match expr {
+        Expr::Literal(literal) => out.push_str(&literal.0),
+        Expr::Variable(v) => out.push_str(&v.name),
+        Expr::Tautology | Expr::Contradiction => out.push_str(style.connective(expr)),
+        Expr::Predicate { pred, args } => {
+            out.push_str(&pred.0);
+            write_args(out, args, style);
+        }
+        Expr::Not(inner) => match inner.as_ref() {
+            Expr::Equals(left, right) => {
+                write_term(out, left, style);
+                out.push_str(&format!(" {} ", style.not_equals()));
+                write_term(out, right, style);
+            }
+            inner => {
+                out.push_str(style.connective(expr));
+                grouped(out, inner, binding(inner) <= 2);
+            }
+        },
+        Expr::And(left, right)
+        | Expr::Or(left, right)
+        | Expr::Xor(left, right)
+        | Expr::Conditional(left, right)
+        | Expr::Biconditional(left, right) => {
+            // none of the connectives are associative, so operands with the same binding need
+            // parentheses too
+            grouped(out, left, binding(left) <= binding(expr));
+            out.push_str(&format!(" {} ", style.connective(expr)));
+            grouped(out, right, binding(right) <= binding(expr));
+        }
+        Expr::UnknownOperator {
+            left,
+            operator,
+            right,
+        } => {
+            grouped(out, left, binding(left) == 0);
+            out.push_str(&format!(" {} ", operator));
+            grouped(out, right, binding(right) == 0);
+        }
+        Expr::Universal { iter, expr: inner } | Expr::Existential { iter, expr: inner } => {
+            out.push_str(style.connective(expr));
+            out.push_str(&iter.name);
+            out.push_str(if style == Style::Latex { "\\, " } else { " " });
+
+            // only these can follow a quantifier without parentheses
+            let bare = matches!(
+                inner.as_ref(),
+                Expr::Literal(_)
+                    | Expr::Predicate { .. }
+                    | Expr::Tautology
+                    | Expr::Contradiction
+                    | Expr::Universal { .. }
+                    | Expr::Existential { .. }
+            );
+            grouped(out, inner, !bare);
+        }
+        Expr::Equals(left, right) => {
+            write_term(out, left, style);
+            out.push_str(" = ");
+            write_term(out, right, style);
+        }
+        Expr::ConstantValue(term) => {
+            let relation = match term {
+                ConstantExpr::Operator(op, left, right) => {
+                    find_relation(op).map(|v| (v, left, right))
+                }
+                _ => None,
+            };
+
+            match relation {
+                Some((relation, left, right)) => {
+                    write_term(out, left, style);
+                    out.push_str(&format!(
+                        " {} ",
+                        style.symbol(relation.symbol, relation.aliases)
+                    ));
+                    write_term(out, right, style);
+                }
+                None => write_term(out, term, style),
+            }
+        }
+        Expr::Invalid => out.push('?'),
+    }
+}
+
+fn write_args(out: &mut String, args: &[ConstantExpr], style: Style) {
+    out.push('(');
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write_term(out, arg, style);
+    }
+    out.push(')');
+}
+
+fn write_term(out: &mut String, term: &ConstantExpr, style: Style) {
+    match term {
+        ConstantExpr::Constant(constant) => out.push_str(&constant.0),
+        ConstantExpr::Variable(v) => out.push_str(&v.name),
+        ConstantExpr::Number(n) => out.push_str(&n.to_string()),
+        ConstantExpr::Function { func, args } => {
+            out.push_str(&func.0);
+            write_args(out, args, style);
+        }
+        ConstantExpr::Operator(op, left, right) => {
+            let operator = TERM_OPERATORS.iter().find(|v| v.symbol == op);
+            let precedence = operator.map(|v| v.precedence).unwrap_or(0);
+            let fixity = operator.map(|v| v.fixity);
+
+            // operators that aren't in the table always get parentheses around their operands
+            let child_precedence = |term: &ConstantExpr| match term {
+                ConstantExpr::Operator(op, ..) => TERM_OPERATORS
+                    .iter()
+                    .find(|v| v.symbol == op)
+                    .filter(|_| operator.is_some())
+                    .map(|v| v.precedence),
+                _ => Some(u8::MAX),
+            };
+            let grouped = |out: &mut String, term: &ConstantExpr, parens: bool| {
+                if parens {
+                    out.push('(');
+                    write_term(out, term, style);
+                    out.push(')');
+                } else {
+                    write_term(out, term, style);
+                }
+            };
+
+            grouped(
+                out,
+                left,
+                child_precedence(left).is_none_or(|v| {
+                    v < precedence || (v == precedence && fixity == Some(Fixity::InfixRight))
+                }),
+            );
+            out.push_str(&format!(
+                " {} ",
+                style.symbol(op, operator.map(|v| v.aliases).unwrap_or_default())
+            ));
+            grouped(
+                out,
+                right,
+                child_precedence(right).is_none_or(|v| {
+                    v < precedence || (v == precedence && fixity != Some(Fixity::InfixRight))
+                }),
+            );
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", print(self, Style::Unicode))
+    }
+}
+
+impl Display for ConstantExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", print_term(self, Style::Unicode))
+    }
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -15,3 +15,490 @@ fn test() {
 
     assert!(err.is_empty());
 }
+
+#[test]
+fn deterministic_variables() {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+    use yggdrasil_grammar::expr::{constantexpr::ConstantExpr, variable::Variable, Expr};
+
+    let parse = |input: &str| {
+        yggdrasil_grammar::PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+    };
+    let hash = |expr: &Expr| {
+        let mut h = DefaultHasher::new();
+        expr.hash(&mut h);
+        h.finish()
+    };
+
+    let input = "@x (/y ((x = y) & (@x P(x))))";
+    let (one, two) = (parse(input), parse(input));
+    assert_eq!(one, two);
+    assert_eq!(hash(&one), hash(&two));
+
+    let Expr::Universal { expr, .. } = one else {
+        unreachable!()
+    };
+    let Expr::Existential { expr, .. } = *expr else {
+        unreachable!()
+    };
+    let Expr::And(left, right) = *expr else {
+        unreachable!()
+    };
+
+    let var = |name: &str, index| {
+        ConstantExpr::Variable(Variable {
+            name: name.to_string(),
+            index: Some(index),
+        })
+    };
+    assert_eq!(*left, Expr::Equals(var("x", 1), var("y", 0)));
+    // the inner x is bound by the closest quantifier
+    let Expr::Universal { expr, .. } = *right else {
+        unreachable!()
+    };
+    assert!(matches!(*expr, Expr::Predicate { args, .. } if args == [var("x", 0)]));
+}
+
+#[test]
+fn relations() {
+    use yggdrasil_grammar::expr::{
+        constantexpr::{Constant, ConstantExpr},
+        Expr,
+    };
+
+    let parse = |input: &str| {
+        let (out, err) =
+            yggdrasil_grammar::PARSER.with(|parser| parser.get().parse(input).into_output_errors());
+        (out, err.iter().map(|v| v.to_string()).collect::<Vec<_>>())
+    };
+    let c = |name: &str| ConstantExpr::Constant(Constant(name.to_string()));
+
+    assert_eq!(parse("a = b").0, Some(Expr::Equals(c("a"), c("b"))));
+    assert_eq!(
+        parse("a ≠ b").0,
+        Some(Expr::Not(Box::new(Expr::Equals(c("a"), c("b")))))
+    );
+    // aliases become the relation's symbol
+    assert_eq!(
+        parse("a <= b").0,
+        Some(Expr::ConstantValue(ConstantExpr::Operator(
+            "≤".to_string(),
+            Box::new(c("a")),
+            Box::new(c("b"))
+        )))
+    );
+
+    assert_eq!(parse("a =< b").1, ["Unknown relation =<"]);
+    // without the whitespace around it
+    let err = yggdrasil_grammar::PARSER.with(|parser| parser.get().parse("a =< b").into_errors());
+    assert_eq!(
+        yggdrasil_grammar::diagnostic::Diagnostic::from(&err[0])
+            .primary
+            .span,
+        2..4
+    );
+
+    // commas separate arguments instead of being read as an operator
+    let Some(Expr::Predicate { args, .. }) = parse("Q(a, f(b, c))").0 else {
+        panic!("expected a predicate");
+    };
+    assert_eq!(
+        args,
+        [
+            c("a"),
+            ConstantExpr::Function {
+                func: Constant("f".to_string()),
+                args: vec![c("b"), c("c")]
+            }
+        ]
+    );
+}
+
+#[test]
+fn term_operators() {
+    use yggdrasil_grammar::expr::{
+        constantexpr::{Constant, ConstantExpr},
+        Expr,
+    };
+
+    let parse = |input: &str| {
+        yggdrasil_grammar::PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+    };
+    let c = |name: &str| ConstantExpr::Constant(Constant(name.to_string()));
+    let op = |op: &str, left, right| {
+        ConstantExpr::Operator(op.to_string(), Box::new(left), Box::new(right))
+    };
+    let s = |arg| ConstantExpr::Function {
+        func: Constant("s".to_string()),
+        args: vec![arg],
+    };
+
+    // precedence
+    assert_eq!(
+        parse("a + b × c = d"),
+        Expr::Equals(op("+", c("a"), op("*", c("b"), c("c"))), c("d"))
+    );
+    assert_eq!(
+        parse("(a + b) * c = d"),
+        Expr::Equals(op("*", op("+", c("a"), c("b")), c("c")), c("d"))
+    );
+
+    // associativity
+    assert_eq!(
+        parse("a − b - c = d"),
+        Expr::Equals(op("-", op("-", c("a"), c("b")), c("c")), c("d"))
+    );
+    assert_eq!(
+        parse("a ^ b ^ c = d"),
+        Expr::Equals(op("^", c("a"), op("^", c("b"), c("c"))), c("d"))
+    );
+
+    // successor
+    assert_eq!(
+        parse("a'' + b = s(s(a)) + b"),
+        Expr::Equals(op("+", s(s(c("a"))), c("b")), op("+", s(s(c("a"))), c("b")))
+    );
+
+    let Expr::Universal { expr, .. } = parse("∀x ∀y (x < y ↔ ∃z ((x + s(z)) = y))") else {
+        panic!("expected a universal");
+    };
+    assert!(matches!(*expr, Expr::Universal { .. }));
+}
+
+#[test]
+fn chains() {
+    use chumsky::cache::Cache;
+    use yggdrasil_grammar::{
+        dialect::{ChainNesting, Dialect},
+        expr::{literal::Literal, Expr},
+        YggdrasilGrammarParserCache,
+    };
+
+    let parse = |chains, input: &str| {
+        let cache = Cache::new(YggdrasilGrammarParserCache {
+            dialect: Dialect { chains },
+        });
+        let (out, err) = cache.get().parse(input).into_output_errors();
+        (out, err.len())
+    };
+    let lit = |name: &str| Box::new(Expr::Literal(Literal(name.to_string())));
+
+    // rejected by default
+    assert_eq!(parse(None, "A & B & C"), (Some(Expr::Invalid), 1));
+
+    assert_eq!(
+        parse(Some(ChainNesting::Left), "A & B & C & D"),
+        (
+            Some(Expr::And(
+                Box::new(Expr::And(Box::new(Expr::And(lit("A"), lit("B"))), lit("C"))),
+                lit("D")
+            )),
+            0
+        )
+    );
+    assert_eq!(
+        parse(Some(ChainNesting::Right), "A ∨ B | C"),
+        (
+            Some(Expr::Or(lit("A"), Box::new(Expr::Or(lit("B"), lit("C"))))),
+            0
+        )
+    );
+
+    // mixed chains, and chains of other operators, still need parentheses
+    assert_eq!(
+        parse(Some(ChainNesting::Left), "A & B | C"),
+        (Some(Expr::Invalid), 1)
+    );
+    assert_eq!(
+        parse(Some(ChainNesting::Left), "A ⊕ B ⊕ C"),
+        (Some(Expr::Invalid), 1)
+    );
+    assert_eq!(
+        parse(Some(ChainNesting::Left), "A -> B -> C"),
+        (Some(Expr::Invalid), 1)
+    );
+}
+
+#[test]
+fn printer() {
+    use yggdrasil_grammar::printer::{print, Style};
+
+    let parse = |input: &str| {
+        yggdrasil_grammar::PARSER.with(|parser| parser.get().parse(input).into_result().unwrap())
+    };
+
+    for input in [
+        "@x (F(x) -> G(x))",
+        "~(P & Q)",
+        "(P & Q) & R",
+        "~(~P)",
+        "~(@x (F(x) | P))",
+        "/x @y R(x, y)",
+        "(P !| Q) <-> 1",
+        "P -> (Q <-> R)",
+        "a != b",
+        "a <= b & f(a, b) > c",
+        "a + b * c = d",
+        "(a + b) * c = d - (e - f)",
+        "a ^ b ^ c = (a ^ b) ^ c",
+        "∀x ∀y (x < y ↔ ∃z ((x + s(z)) = y))",
+    ] {
+        let expr = parse(input);
+        for style in [Style::Unicode, Style::Ascii] {
+            assert_eq!(parse(&print(&expr, style)), expr, "{input} in {style:?}");
+        }
+    }
+
+    assert_eq!(
+        parse("~(P & Q) -> @x (F(x))").to_string(),
+        "¬(P ∧ Q) → ∀x F(x)"
+    );
+    assert_eq!(
+        print(&parse("a + b * c = d"), Style::Ascii),
+        "a + b * c = d"
+    );
+    assert_eq!(
+        print(&parse("@x (x <= a -> ~P)"), Style::Latex),
+        "\\forall x\\, (x \\leq a \\to \\neg P)"
+    );
+}
+
+#[test]
+fn spans() {
+    use yggdrasil_grammar::{
+        expr::{
+            constantexpr::{Constant, ConstantExpr},
+            literal::Literal,
+            spanned::{Spanned, SpannedConstantExpr, SpannedExpr},
+            Expr,
+        },
+        PARSER, SPANNED_PARSER,
+    };
+
+    let input = "~P(a) & @x ( F(x) -> x = f(a, b) )";
+    let spanned = SPANNED_PARSER.with(|parser| parser.get().parse(input).into_result().unwrap());
+    let text = |span: chumsky::span::SimpleSpan| &input[span.into_range()];
+
+    assert_eq!(
+        Some(spanned.strip()),
+        PARSER.with(|parser| parser.get().parse(input).into_output())
+    );
+    assert_eq!(text(spanned.span), input);
+
+    let SpannedExpr::And(left, right) = &spanned.node else {
+        panic!("expected a conjunction");
+    };
+    assert_eq!(text(left.span), "~P(a)");
+    assert_eq!(text(right.span), "@x ( F(x) -> x = f(a, b) )");
+
+    let SpannedExpr::Universal { expr, .. } = &right.node else {
+        panic!("expected a universal");
+    };
+    assert_eq!(text(expr.span), "( F(x) -> x = f(a, b) )");
+
+    let SpannedExpr::Conditional(_, identity) = &expr.node else {
+        panic!("expected a conditional");
+    };
+    let SpannedExpr::Equals(_, Spanned { node, span }) = &identity.node else {
+        panic!("expected an identity");
+    };
+    assert!(matches!(node, SpannedConstantExpr::Function { .. }));
+    assert_eq!(text(*span), "f(a, b)");
+
+    let target = Expr::Predicate {
+        pred: Literal("P".to_string()),
+        args: vec![ConstantExpr::Constant(Constant("a".to_string()))],
+    };
+    assert_eq!(spanned.span_of(&target).map(text), Some("P(a)"));
+    assert_eq!(spanned.span_of(&Expr::Tautology), None);
+}
+
+#[test]
+fn recovery() {
+    use std::ops::Range;
+    use yggdrasil_grammar::diagnostic::Diagnostic;
+
+    // the statement that was parsed, and the message and span of each error
+    let parse = |input: &str| {
+        let (out, err) =
+            yggdrasil_grammar::PARSER.with(|parser| parser.get().parse(input).into_output_errors());
+        (
+            out.map(|v| v.to_string()),
+            err.iter()
+                .map(Diagnostic::from)
+                .map(|v| (v.message, v.primary.span))
+                .collect::<Vec<_>>(),
+        )
+    };
+    let partial = |expr: &str, errors: &[(&str, Range<usize>)]| {
+        (
+            Some(expr.to_string()),
+            errors
+                .iter()
+                .map(|(message, span)| (message.to_string(), span.clone()))
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    // missing operands
+    assert_eq!(
+        parse("P &"),
+        partial("P ∧ ?", &[("Missing operand after &", 2..3)])
+    );
+    assert_eq!(
+        parse("P -> Q & "),
+        partial("P → Q ∧ ?", &[("Missing operand after &", 7..8)])
+    );
+    assert_eq!(
+        parse("P | (Q & )"),
+        partial("P ∨ (Q ∧ ?)", &[("Missing operand after &", 7..8)])
+    );
+
+    // unbalanced brackets
+    assert_eq!(
+        parse("(P & Q"),
+        partial("P ∧ Q", &[("Unclosed bracket (", 0..1)])
+    );
+    assert_eq!(
+        parse("(P & (Q | R) -> S"),
+        partial("P ∧ (Q ∨ R) → S", &[("Unclosed bracket (", 0..1)])
+    );
+    assert_eq!(
+        parse("[P & %] -> Q"),
+        partial(
+            "P ∧ ? → Q",
+            &[("Missing operand after &", 3..4), ("Unexpected %", 5..6)]
+        )
+    );
+    assert_eq!(
+        parse("@x (F(x) & "),
+        partial(
+            "∀x (F(x) ∧ ?)",
+            &[
+                ("Missing operand after &", 9..10),
+                ("Unclosed bracket (", 3..4)
+            ]
+        )
+    );
+    assert_eq!(
+        parse("(%) & P"),
+        partial("? ∧ P", &[("Expected a statement", 0..3)])
+    );
+
+    // stray tokens
+    assert_eq!(parse("P & Q)"), partial("P ∧ Q", &[("Unexpected )", 5..6)]));
+    assert_eq!(parse(") P"), partial("P", &[("Unexpected )", 0..1)]));
+    assert_eq!(parse("%% & P"), partial("P", &[("Unexpected %% &", 0..4)]));
+    assert_eq!(
+        parse("(P Q) & R"),
+        partial("P ∧ R", &[("Unexpected Q", 3..4)])
+    );
+    assert_eq!(
+        parse("P & ) Q"),
+        partial(
+            "P ∧ ?",
+            &[("Missing operand after &", 2..3), ("Unexpected ) Q", 4..7)]
+        )
+    );
+
+    assert_eq!(parse(""), partial("?", &[("Expected a statement", 0..0)]));
+    assert_eq!(parse("(a = b) & P"), partial("a = b ∧ P", &[]));
+}
+
+#[test]
+fn diagnostics() {
+    use yggdrasil_grammar::diagnostic::{Diagnostic, DiagnosticCode, Severity};
+
+    let diagnose = |input: &str| {
+        let (_, err) =
+            yggdrasil_grammar::PARSER.with(|parser| parser.get().parse(input).into_output_errors());
+        err.iter().map(Diagnostic::from).collect::<Vec<_>>()
+    };
+
+    let input = "A & B | C";
+    let [diagnostic] = &diagnose(input)[..] else {
+        panic!("expected one diagnostic");
+    };
+    assert_eq!(diagnostic.code, DiagnosticCode::NotAssociative);
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.primary.span, 2..3);
+    assert_eq!(diagnostic.secondary.len(), 1);
+    assert_eq!(diagnostic.secondary[0].span, 6..7);
+    assert_eq!(diagnostic.suggestions[0].apply(input), "(A & B) | C");
+    assert_eq!(
+        diagnostic.render_plain(input),
+        "error[E003]: These operators are not associative; use parentheses to indicate order of operation
+ --> 1:3
+  |
+1 | A & B | C
+  |   ^
+  |       -
+  | = help: group the first two operands: (A & B) | C
+"
+    );
+    assert!(diagnostic
+        .render_ansi(input)
+        .starts_with("\x1b[1;31merror[E003]\x1b[0m"));
+
+    let input = "∀x F(y)";
+    let [diagnostic] = &diagnose(input)[..] else {
+        panic!("expected one diagnostic");
+    };
+    assert_eq!(diagnostic.code, DiagnosticCode::UnboundVariable);
+    assert_eq!(diagnostic.notes.len(), 1);
+    let squiggles = diagnostic.squiggles();
+    assert_eq!(squiggles.len(), 1);
+    assert_eq!(&input[squiggles[0].span.clone()], "y");
+
+    let input = "P & Q)";
+    let [diagnostic] = &diagnose(input)[..] else {
+        panic!("expected one diagnostic");
+    };
+    assert_eq!(diagnostic.code, DiagnosticCode::UnexpectedInput);
+    assert_eq!(diagnostic.suggestions[0].apply(input), "P & Q");
+}
+
+#[test]
+fn unknown_operators() {
+    use yggdrasil_grammar::{
+        diagnostic::{Diagnostic, DiagnosticCode},
+        expr::Expr,
+    };
+
+    let tests = [
+        ("P => Q", Some("P → Q")),
+        ("P <=> Q", Some("P ↔ Q")),
+        ("P ^ Q", Some("P ∧ Q")),
+        ("P v Q", Some("P ∨ Q")),
+        ("P && Q", Some("P ∧ Q")),
+        ("P > Q", Some("P → Q")),
+        ("P & Q => R", Some("P & Q → R")),
+        ("P ~ Q", None),
+    ];
+
+    for (input, fixed) in tests {
+        let (out, err) =
+            yggdrasil_grammar::PARSER.with(|parser| parser.get().parse(input).into_output_errors());
+        let [diagnostic] = &err.iter().map(Diagnostic::from).collect::<Vec<_>>()[..] else {
+            panic!("expected one diagnostic for {}", input);
+        };
+
+        assert!(
+            matches!(out, Some(Expr::UnknownOperator { .. })),
+            "{}",
+            input
+        );
+        assert_eq!(
+            diagnostic.code,
+            DiagnosticCode::UnknownOperator,
+            "{}",
+            input
+        );
+        assert_eq!(
+            diagnostic.suggestions.first().map(|v| v.apply(input)),
+            fixed.map(str::to_string),
+            "{}",
+            input
+        );
+    }
+}
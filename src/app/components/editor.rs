@@ -2,6 +2,8 @@ pub mod branch;
 mod statement;
 pub mod status;
 mod toolbar;
+mod truth_table;
 
 pub use statement::*;
 pub use toolbar::*;
+pub use truth_table::*;
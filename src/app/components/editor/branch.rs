@@ -2,7 +2,7 @@ use super::StatementState;
 use crate::app::{
     components::editor::{
         status::{StatusIndicator, StatusLevel},
-        DiagnosticsSlot, InfoSlot, StatementEditor,
+        DiagnosticsSlot, InfoSlot, StatementEditor, StatementError,
     },
     pages::editor::EditorContext,
     util::{hotkeys::use_hotkey, uid::Uid},
@@ -10,13 +10,14 @@ use crate::app::{
 use indexmap::IndexMap;
 use leptos::{prelude::*, reactive::graph::ReactiveNode};
 use leptos_use::sync_signal;
-use std::{cmp::max, fmt::Display, iter, ops::Deref, time::Duration};
+use std::{cmp::max, collections::HashMap, fmt::Display, iter, ops::Deref, time::Duration};
 use strum::IntoEnumIterator;
 use tracing::{info, warn};
 use yggdrasil_engine::{
+    closure::find_closure,
     error::EngineError,
-    rules::branch::BranchRule,
-    util::{expr_list_starts_with, expr_maybe_list_starts_with},
+    rules::branch::{check_sub_branches, BranchRule, SubBranchError},
+    tree::{BranchId, NodeId, Tree},
 };
 
 /// An error a branch can have
@@ -88,28 +89,22 @@ impl BranchState {
                     if let Some(decomposed_rule) = decomposed_rule.read().deref() =>
                 {
                     match decomposed_rule {
-                        Ok((correct1, correct2)) => {
+                        Ok(correct) => {
                             let sub1_statements = sub1.statements.read();
                             let sub1_iter = sub1_statements.iter().map(|(_, v)| v.expr().read());
 
                             let sub2_statements = sub2.statements.read();
                             let sub2_iter = sub2_statements.iter().map(|(_, v)| v.expr().read());
 
-                            let one = expr_maybe_list_starts_with(sub1_iter.clone(), correct1);
-                            let two = expr_maybe_list_starts_with(sub1_iter, correct2);
-
-                            let three = expr_maybe_list_starts_with(sub2_iter.clone(), correct1);
-                            let four = expr_maybe_list_starts_with(sub2_iter, correct2);
-
-                            match (one, two, three, four) {
-                                (true, _, _, true) | (_, true, true, _) => None,
-                                (true, _, _, _) | (_, true, _, _) => {
-                                    Some(BranchError::SubBranch(sub2.uid().get()))
-                                }
-                                (_, _, true, _) | (_, _, _, true) => {
+                            match check_sub_branches(correct, sub1_iter, sub2_iter) {
+                                None => None,
+                                Some(SubBranchError::First) => {
                                     Some(BranchError::SubBranch(sub1.uid().get()))
                                 }
-                                _ => Some(BranchError::SubBranches),
+                                Some(SubBranchError::Second) => {
+                                    Some(BranchError::SubBranch(sub2.uid().get()))
+                                }
+                                Some(SubBranchError::Both) => Some(BranchError::SubBranches),
                             }
                         }
                         Err(err) => Some(BranchError::Root(err.clone())),
@@ -239,6 +234,59 @@ impl BranchState {
     pub fn current_error(&self) -> Signal<Option<BranchError>> {
         self.current_error
     }
+
+    /// Converts this branch and its sub-branches into an engine [Tree], along with the statement
+    /// each node came from. Each node other than the premises and the results of a split is
+    /// annotated with a rule that derives it from a statement above it, if there is one. Fails if
+    /// any statement doesn't parse.
+    pub fn to_tree(&self) -> Result<(Tree, HashMap<NodeId, Uid>), BranchError> {
+        let mut tree = Tree::new();
+        let mut uids = HashMap::new();
+        let root = tree.root();
+        self.add_to_tree(&mut tree, root, &mut uids)?;
+        Ok((tree, uids))
+    }
+
+    fn add_to_tree(
+        &self,
+        tree: &mut Tree,
+        id: BranchId,
+        uids: &mut HashMap<NodeId, Uid>,
+    ) -> Result<(), BranchError> {
+        let mut statements = Vec::new();
+        for (uid, statement) in self.statements.read_untracked().iter() {
+            if matches!(
+                statement.current_error().get_untracked(),
+                Some(StatementError::Parsing { .. })
+            ) {
+                return Err(BranchError::DependentStatementError);
+            }
+            let expr = statement
+                .expr()
+                .get_untracked()
+                .ok_or(BranchError::DependentStatementError)?;
+            statements.push((uid.clone(), expr));
+        }
+
+        let (statement_uids, exprs): (Vec<_>, Vec<_>) = statements.into_iter().unzip();
+        let nodes = tree.add_statements(id, exprs);
+        let last = nodes.last().copied();
+        uids.extend(nodes.into_iter().zip(statement_uids));
+
+        if let Some((one, two)) = self.sub.get_untracked() {
+            let rule = self
+                .branch_rule
+                .get_untracked()
+                .ok_or(BranchError::NoRuleSelected)?;
+            let source = last.ok_or(BranchError::NoStatements)?;
+
+            let (first, second) = tree.split(id, rule, source).map_err(BranchError::Root)?;
+            one.get_untracked().add_to_tree(tree, first, uids)?;
+            two.get_untracked().add_to_tree(tree, second, uids)?;
+        }
+
+        Ok(())
+    }
 }
 
 /// Component to render a branch, its statements, and its sub-branches
@@ -256,6 +304,39 @@ pub fn Branch(
 
     let showing_branch_rule_selector = RwSignal::new(false);
 
+    // the input of the statements that close this branch, if any
+    let closed_by = Memo::new(move |_| {
+        let mut path = vec![branch];
+        ctx.branches.with(|branches| {
+            while let Some(parent) = path.last().unwrap().parent {
+                match branches.get(&parent.get()) {
+                    Some(parent) => path.push(*parent),
+                    None => break,
+                }
+            }
+        });
+
+        let statements = path
+            .iter()
+            .rev()
+            .flat_map(|b| {
+                b.statements
+                    .read()
+                    .values()
+                    .filter_map(|s| Some((*s, s.expr().get()?)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        find_closure(statements.iter().map(|(_, expr)| expr)).map(|closure| {
+            closure
+                .statements()
+                .into_iter()
+                .map(|i| statements[i].0.raw().get())
+                .collect::<Vec<_>>()
+        })
+    });
+
     use_hotkey("ctrl+b", move |ev| {
         if let (Some(focused_statement), Some((last_statement, _))) = (
             ctx.focused_statement.read().as_ref(),
@@ -391,6 +472,19 @@ pub fn Branch(
                     }
                 }
             />
+            <Show when=move || closed_by.read().is_some() && branch.sub.read().is_none()>
+                <div
+                    class="pl-2 font-sans text-2xl font-bold text-white select-none"
+                    title=move || {
+                        closed_by
+                            .get()
+                            .map(|v| format!("Closed by {}", v.join(" and ")))
+                            .unwrap_or_default()
+                    }
+                >
+                    "×"
+                </div>
+            </Show>
             {move || {
                 branch
                     .sub
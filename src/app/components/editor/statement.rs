@@ -1,30 +1,25 @@
-use crate::app::{
-    components::editor::status::{StatusIndicator, StatusLevel},
-    util::hotkeys::use_hotkey,
-};
-use ariadne::{
-    Cache, Color, Config, FileCache, FnCache, IndexType, Label, Report, ReportBuilder, ReportKind,
-    Source,
-};
-use leptos::html::{HtmlElement, Input};
+use crate::app::components::editor::status::{StatusIndicator, StatusLevel};
+use leptos::html::Input;
 use leptos::prelude::*;
-use leptos::{either::EitherOf3, ev::InputEvent};
-use leptos_use::{core::IntoElementMaybeSignal, signal_debounced};
-use std::{
-    cell::LazyCell, collections::HashMap, fmt::Display, io::BufWriter, ops::Range, sync::LazyLock,
-};
-use strum::IntoEnumIterator;
-use tracing::{debug, info};
-use wasm_bindgen::JsCast;
+use leptos_use::signal_debounced;
+use std::{collections::HashMap, fmt::Display, sync::LazyLock};
+use tracing::info;
 use web_sys::HtmlInputElement;
-use yggdrasil_engine::{error::EngineError, rules::branch::BranchRule};
-use yggdrasil_grammar::{expr::Expr, Parser, PARSER};
+use yggdrasil_engine::error::EngineError;
+use yggdrasil_grammar::{
+    diagnostic::{Diagnostic, Severity, Squiggle},
+    expr::Expr,
+    Parser, PARSER,
+};
 
 /// An error a statement can have
 #[derive(Clone, Debug, PartialEq)]
 pub enum StatementError {
     /// The statement was not parsable
-    Parsing(String),
+    Parsing {
+        source: String,
+        diagnostics: Vec<Diagnostic>,
+    },
 
     /// There is as logic problem with the statement
     Logic(EngineError),
@@ -33,7 +28,17 @@ pub enum StatementError {
 impl Display for StatementError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Parsing(err) => write!(f, "{}", err),
+            Self::Parsing {
+                source,
+                diagnostics,
+            } => write!(
+                f,
+                "{}",
+                diagnostics
+                    .iter()
+                    .map(|v| v.render_plain(source))
+                    .collect::<String>()
+            ),
             Self::Logic(engine_error) => {
                 write!(f, "Evaluation error: {}", engine_error)
             }
@@ -48,7 +53,7 @@ pub struct StatementState {
     raw: RwSignal<String>,
     // whatever error the statement has
     current_error: Signal<Option<StatementError>>,
-    // the parsed expr tree, if the raw input was parsed without error
+    // the parsed expr tree, with Expr::Invalid holes where the raw input could not be parsed
     expr: Signal<Option<Expr>>,
     // if the statement is currently focused in the editor
     focused: Signal<bool>,
@@ -61,65 +66,45 @@ impl StatementState {
         let raw = RwSignal::new(String::new());
 
         let raw_debounced: Signal<String> = signal_debounced(raw, 100.0);
-        let expr = Memo::new(move |_| {
+        let parsed = Memo::new(move |_| {
             raw_debounced.with(|raw| {
                 let raw = raw.clone();
                 PARSER.with(move |parser| {
                     let parser = parser.get();
-                    let res = parser.parse(&raw).into_result().map_err(|errs| {
-                        errs.into_iter()
-                            .map(|err| {
-                                let mut buf = BufWriter::new(Vec::new());
-
-                                let report = Report::build(ReportKind::Error, ("", (&err).into()))
-                                    .with_config(Config::default().with_index_type(IndexType::Byte))
-                                    .with_message(err.reason().to_string())
-                                    .with_labels(err.spans().map(|s| {
-                                        Label::new(("", s.into_range())).with_color(Color::Red)
-                                    }))
-                                    .finish();
-
-                                report
-                                    .write_for_stdout(("", Source::from(raw.clone())), &mut buf)
-                                    .unwrap();
-
-                                ansi_to_html::convert(
-                                    String::from_utf8_lossy(buf.buffer()).as_ref(),
-                                )
-                                .unwrap_or("error".to_string())
-                            })
-                            .fold(String::new(), |a, v| a + &v)
+                    let (expr, errs) = parser.parse(&raw).into_output_errors();
+                    let error = (!errs.is_empty()).then(|| StatementError::Parsing {
+                        diagnostics: errs.iter().map(Diagnostic::from).collect(),
+                        source: raw.clone(),
                     });
 
-                    let _ = res
+                    let _ = expr
                         .as_ref()
-                        .inspect(|res| info!("parsed expression: {:#?}", res));
+                        .inspect(|expr| info!("parsed expression: {:#?}", expr));
 
-                    res
+                    (expr, error)
                 })
             })
         });
 
-        let error = Memo::new(move |_| {
-            let expr = expr.read();
-            match expr.as_ref() {
-                Ok(_) => None,
-                Err(err) => Some(StatementError::Parsing(err.clone())),
-            }
-        });
+        let error = Memo::new(move |_| parsed.read().1.clone());
 
         Self {
             raw,
             current_error: error.into(),
-            expr: Signal::derive(move || expr.get().ok()),
+            expr: Signal::derive(move || parsed.read().0.clone()),
             focused,
             highlighted,
         }
     }
 
+    pub fn raw(&self) -> &RwSignal<String> {
+        &self.raw
+    }
+
     pub fn expr(&self) -> &Signal<Option<Expr>> {
         &self.expr
     }
+
     pub fn current_error(&self) -> &Signal<Option<StatementError>> {
         &self.current_error
     }
@@ -139,6 +124,48 @@ static INPUT_MAPPINGS: LazyLock<HashMap<&str, &str>> = LazyLock::new(|| {
     ])
 });
 
+/// Splits a statement into pieces, along with how severe the worst squiggle under each one is
+fn squiggled(source: &str, squiggles: &[Squiggle]) -> Vec<(String, Option<Severity>)> {
+    // squiggles with nothing under them, like an empty statement, underline the next character,
+    // which is an extra space at the end of the statement
+    let text = format!("{} ", source);
+    let spans = squiggles
+        .iter()
+        .map(|v| {
+            let start = v.span.start.min(source.len());
+            let end = if v.span.is_empty() {
+                start + text[start..].chars().next().map_or(0, char::len_utf8)
+            } else {
+                v.span.end.min(source.len())
+            };
+            (start..end, v.severity)
+        })
+        .collect::<Vec<_>>();
+
+    let mut bounds = spans
+        .iter()
+        .flat_map(|(v, _)| [v.start, v.end])
+        .chain([0, text.len()])
+        .collect::<Vec<_>>();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    bounds
+        .windows(2)
+        .map(|v| {
+            let severity = spans
+                .iter()
+                .filter(|(span, _)| span.start <= v[0] && v[1] <= span.end)
+                .map(|(_, severity)| *severity)
+                .min_by_key(|v| match v {
+                    Severity::Error => 0,
+                    Severity::Warning => 1,
+                });
+            (text[v[0]..v[1]].to_string(), severity)
+        })
+        .collect()
+}
+
 #[derive(Default)]
 #[slot]
 pub struct InfoSlot {
@@ -178,67 +205,130 @@ pub fn StatementEditor(
             on:mousedown=move |_| on_focus.run(())
             on:focus=move |_| on_focus.run(())
         >
-            <input
-                node_ref=input_box
-                type="text"
-                on:keydown=move |ev| {
-                    if INPUT_MAPPINGS.contains_key(ev.key().as_str()) {
-                        ev.prevent_default();
-                        let new_input = INPUT_MAPPINGS.get(ev.key().as_str()).unwrap();
-                        let el = event_target::<HtmlInputElement>(&ev);
-                        let selected_range = el
-                            .selection_start()
-                            .unwrap()
-                            .map(|v| v as usize)
-                            .unwrap_or(
-                                statement.raw.get().len(),
-                            )..el
-                            .selection_end()
-                            .unwrap()
-                            .map(|v| v as usize)
-                            .unwrap_or(statement.raw.get().len());
-                        statement
-                            .raw
-                            .update(|v| {
-                                let mut char_indices = v.char_indices();
-                                let last_char_pos = match v.char_indices().last() {
-                                    Some(last_char) => last_char.0 + last_char.1.len_utf8(),
-                                    None => 0,
-                                };
-                                let char_start = char_indices
-                                    .nth(selected_range.start)
-                                    .map(|(x, _)| x)
-                                    .unwrap_or(last_char_pos);
-                                let char_end = if selected_range.end - selected_range.start == 0 {
-                                    char_start
-                                } else {
-                                    char_indices
-                                        .nth(selected_range.end - selected_range.start)
+            <div class="relative">
+                <input
+                    node_ref=input_box
+                    type="text"
+                    on:keydown=move |ev| {
+                        if INPUT_MAPPINGS.contains_key(ev.key().as_str()) {
+                            ev.prevent_default();
+                            let new_input = INPUT_MAPPINGS.get(ev.key().as_str()).unwrap();
+                            let el = event_target::<HtmlInputElement>(&ev);
+                            let selected_range = el
+                                .selection_start()
+                                .unwrap()
+                                .map(|v| v as usize)
+                                .unwrap_or(
+                                    statement.raw.get().len(),
+                                )..el
+                                .selection_end()
+                                .unwrap()
+                                .map(|v| v as usize)
+                                .unwrap_or(statement.raw.get().len());
+                            statement
+                                .raw
+                                .update(|v| {
+                                    let mut char_indices = v.char_indices();
+                                    let last_char_pos = match v.char_indices().last() {
+                                        Some(last_char) => last_char.0 + last_char.1.len_utf8(),
+                                        None => 0,
+                                    };
+                                    let char_start = char_indices
+                                        .nth(selected_range.start)
                                         .map(|(x, _)| x)
-                                        .unwrap_or(last_char_pos)
-                                };
-                                v.replace_range(char_start..char_end, new_input);
-                            });
-                        let _ = el
-                            .set_selection_range(
-                                selected_range.start as u32 + 1,
-                                selected_range.start as u32 + 1,
-                            );
+                                        .unwrap_or(last_char_pos);
+                                    let char_end = if selected_range.end - selected_range.start == 0 {
+                                        char_start
+                                    } else {
+                                        char_indices
+                                            .nth(selected_range.end - selected_range.start)
+                                            .map(|(x, _)| x)
+                                            .unwrap_or(last_char_pos)
+                                    };
+                                    v.replace_range(char_start..char_end, new_input);
+                                });
+                            let _ = el
+                                .set_selection_range(
+                                    selected_range.start as u32 + 1,
+                                    selected_range.start as u32 + 1,
+                                );
+                        }
                     }
-                }
-                // on:input=move |ev| {
-                // let ev = ev.dyn_ref::<InputEvent>().unwrap();
-                // let new_txt = event_target_value(ev);
-                // statement.raw.set(new_txt);
-                // }
-                on:focus=move |_| on_focus.run(())
-                bind:value=statement.raw
-            />
+                    on:focus=move |_| on_focus.run(())
+                    bind:value=statement.raw
+                />
+                // the statement again, invisible except for squiggles under its problems
+                <div
+                    aria-hidden="true"
+                    class="absolute inset-0 text-transparent whitespace-pre pointer-events-none"
+                >
+                    {move || {
+                        statement
+                            .current_error
+                            .with(|error| match error {
+                                Some(StatementError::Parsing { source, diagnostics }) => {
+                                    let squiggles = diagnostics
+                                        .iter()
+                                        .flat_map(Diagnostic::squiggles)
+                                        .collect::<Vec<_>>();
+                                    Some(
+                                        squiggled(source, &squiggles)
+                                            .into_iter()
+                                            .map(|(text, severity)| {
+                                                view! {
+                                                    <span class=match severity {
+                                                        Some(Severity::Error) => {
+                                                            "underline decoration-wavy decoration-red-500"
+                                                        }
+                                                        Some(Severity::Warning) => {
+                                                            "underline decoration-wavy decoration-yellow-500"
+                                                        }
+                                                        None => "",
+                                                    }>{text}</span>
+                                                }
+                                            })
+                                            .collect_view(),
+                                    )
+                                }
+                                _ => None,
+                            })
+                    }}
+                </div>
+            </div>
+
+            // fixes the parser suggested, applied with a click
+            {move || {
+                statement
+                    .current_error
+                    .with(|error| match error {
+                        Some(StatementError::Parsing { source, diagnostics }) => {
+                            Some(
+                                diagnostics
+                                    .iter()
+                                    .flat_map(|v| v.suggestions.iter())
+                                    .map(|suggestion| {
+                                        let fixed = suggestion.apply(source);
+                                        view! {
+                                            <button
+                                                class="px-1 text-sm rounded bg-cyan-800 hover:bg-cyan-700"
+                                                title=fixed.clone()
+                                                on:click=move |_| statement.raw.set(fixed.clone())
+                                            >
+                                                {suggestion.message.clone()}
+                                            </button>
+                                        }
+                                    })
+                                    .collect_view(),
+                            )
+                        }
+                        _ => None,
+                    })
+            }}
 
             {info_slot.children.map(|children| children())}
 
             <StatusIndicator state=Signal::derive(move || match statement.current_error.get() {
-                Some(StatementError::Parsing(_)) => StatusLevel::Warn,
+                Some(StatementError::Parsing { .. }) => StatusLevel::Warn,
                 Some(StatementError::Logic(_)) => StatusLevel::Bad,
                 None => StatusLevel::Good,
             }) />
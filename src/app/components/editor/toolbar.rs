@@ -38,6 +38,7 @@ pub enum ToolbarEvent {
     EvaluationOptions,
     CheckStatement,
     CheckTree,
+    ToggleTruthTable,
     ShortcutOptions,
     SubstitutionOptions,
     OpenUserGuide,
@@ -123,6 +124,8 @@ pub fn Toolbar<ToolbarF: Fn(ToolbarEvent) + Copy + 'static>(callback: ToolbarF)
                 None,
                 Some(("Check statement", ToolbarEvent::CheckStatement)),
                 Some(("Check tree", ToolbarEvent::CheckTree)),
+                None,
+                Some(("Toggle truth table", ToolbarEvent::ToggleTruthTable)),
             ],
         ),
         (
@@ -0,0 +1,69 @@
+use leptos::{either::EitherOf3, prelude::*};
+use yggdrasil_engine::truth_table::TruthTable;
+use yggdrasil_grammar::expr::Expr;
+
+/// Component to render the truth table of a propositional statement
+#[component]
+pub fn TruthTableView(#[prop(into)] expr: Signal<Option<Expr>>) -> impl IntoView {
+    let table = Memo::new(move |_| expr.read().as_ref().map(TruthTable::new));
+
+    let cell = |value: bool| {
+        view! {
+            <td class=if value { "px-2 text-green-400" } else { "px-2 text-red-400" }>
+                {if value { "T" } else { "F" }}
+            </td>
+        }
+    };
+
+    view! {
+        <div class="flex flex-col gap-2 items-start p-2 font-mono text-white">
+            {move || match table.get() {
+                None => EitherOf3::A(view! { <p>"Select a statement that has no errors"</p> }),
+                Some(Err(err)) => EitherOf3::B(view! { <p>{err.to_string()}</p> }),
+                Some(Ok(table)) => {
+                    EitherOf3::C(
+                        view! {
+                            <table class="text-center border-collapse">
+                                <thead>
+                                    <tr class="border-b-2 border-white">
+                                        {table
+                                            .atoms
+                                            .iter()
+                                            .map(|v| view! { <th class="px-2">{v.clone()}</th> })
+                                            .collect_view()}
+                                        {table
+                                            .columns
+                                            .iter()
+                                            .map(|v| {
+                                                view! {
+                                                    <th class="px-2 border-l border-white">
+                                                        {v.to_string()}
+                                                    </th>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {table
+                                        .rows
+                                        .iter()
+                                        .map(|row| {
+                                            view! {
+                                                <tr>
+                                                    {row.valuation.iter().map(|v| cell(*v)).collect_view()}
+                                                    {row.values.iter().map(|v| cell(*v)).collect_view()}
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </tbody>
+                            </table>
+                            <p>"This statement is " {table.classification().to_string().to_lowercase()}</p>
+                        },
+                    )
+                }
+            }}
+        </div>
+    }
+}
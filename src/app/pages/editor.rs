@@ -3,13 +3,17 @@ use std::{collections::HashMap, ops::Deref};
 use crate::app::{
     components::editor::{
         branch::{Branch, BranchError, BranchState},
-        StatementEditor, StatementError, StatementState, Toolbar, ToolbarEvent,
+        StatementEditor, StatementError, StatementState, Toolbar, ToolbarEvent, TruthTableView,
     },
     util::{hotkeys::use_hotkey, uid::Uid},
 };
 use indexmap::IndexMap;
 use leptos::prelude::*;
 use tracing::{info, warn};
+use yggdrasil_engine::{
+    completeness::{branch_constants, check_completeness, is_decomposed, Verdict},
+    tree::{NodeId, Tree},
+};
 
 /// Struct to contain the current editor state
 #[derive(Clone, Copy, Debug)]
@@ -28,6 +32,83 @@ pub struct EditorContext {
 
     /// Every statement currently in the editor
     pub statements: RwSignal<HashMap<Uid, StatementState>>,
+
+    /// The result of the last check the user asked for, cleared when the focus changes
+    pub report: RwSignal<Option<String>>,
+
+    /// Whether the truth table of the focused statement is shown
+    pub showing_truth_table: RwSignal<bool>,
+}
+
+/// Describes whether the whole tree is finished, and what is left to decompose if not.
+/// Trees with a statement that doesn't follow from anything above it aren't given a verdict.
+fn check_tree(tree: &Tree, uids: &HashMap<NodeId, Uid>, ctx: &EditorContext) -> String {
+    let raw = |node: &NodeId| {
+        ctx.statements.with_untracked(|s| {
+            s.get(&uids[node])
+                .map(|v| v.raw().get_untracked())
+                .unwrap_or_default()
+        })
+    };
+
+    let problems = yggdrasil_engine::tree::check_tree(tree);
+    if !problems.is_empty() {
+        let mut message = "The tree has mistakes, so it can't be checked".to_string();
+        for (node, errors) in problems {
+            for error in errors {
+                message += &format!("\n{}: {}", raw(&node), error);
+            }
+        }
+        return message;
+    }
+
+    let report = check_completeness(tree);
+
+    let mut message = match report.verdict {
+        Verdict::Valid => "Every branch is closed, so the tree is valid".to_string(),
+        Verdict::Invalid(_) => {
+            "An open branch is fully decomposed, so the tree is invalid".to_string()
+        }
+        Verdict::Incomplete => "The tree is not finished".to_string(),
+    };
+
+    let mut seen = Vec::new();
+    for undecomposed in report.undecomposed {
+        if !seen.contains(&undecomposed.node) {
+            seen.push(undecomposed.node);
+            message += &format!("\nNot decomposed: {}", raw(&undecomposed.node));
+        }
+    }
+
+    message
+}
+
+/// Describes whether one statement has been decomposed on every open branch it is on
+fn check_statement(tree: &Tree, node: NodeId) -> String {
+    let open = tree
+        .leaves()
+        .filter(|leaf| tree.closure(leaf.id).is_none())
+        .map(|leaf| tree.path(leaf.id))
+        .filter(|path| path.contains(&node))
+        .collect::<Vec<_>>();
+
+    let undecomposed = open
+        .iter()
+        .filter(|path| {
+            let exprs = path.iter().map(|v| &tree.node(*v).expr).collect::<Vec<_>>();
+            let constants = branch_constants(exprs.iter().copied());
+            !is_decomposed(&tree.node(node).expr, &exprs, &constants)
+        })
+        .count();
+
+    match (open.len(), undecomposed) {
+        (0, _) => "Every branch with this statement is closed".to_string(),
+        (_, 0) => "Statement is decomposed on every open branch".to_string(),
+        (total, n) => format!(
+            "Statement is not decomposed on {} of {} open branches",
+            n, total
+        ),
+    }
 }
 
 fn handle_toolbar_event(ev: ToolbarEvent, ctx: &EditorContext) {
@@ -61,6 +142,31 @@ fn handle_toolbar_event(ev: ToolbarEvent, ctx: &EditorContext) {
                 });
             });
         }
+        ToolbarEvent::CheckTree | ToolbarEvent::CheckStatement => {
+            let root = ctx.root_branch.get_untracked();
+            let converted = ctx
+                .branches
+                .with_untracked(|branches| branches.get(&root).unwrap().to_tree());
+
+            let message = match converted {
+                Err(err) => format!("Could not check tree: {}", err),
+                Ok((tree, uids)) if matches!(ev, ToolbarEvent::CheckTree) => {
+                    check_tree(&tree, &uids, ctx)
+                }
+                Ok((tree, uids)) => {
+                    let focused = ctx.focused_statement.get_untracked();
+                    match uids.iter().find(|(_, uid)| Some(*uid) == focused.as_ref()) {
+                        Some((node, _)) => check_statement(&tree, *node),
+                        None => "No statement selected".to_string(),
+                    }
+                }
+            };
+
+            ctx.report.set(Some(message));
+        }
+        ToolbarEvent::ToggleTruthTable => {
+            ctx.showing_truth_table.update(|v| *v = !*v);
+        }
         _ => (),
     }
 }
@@ -82,6 +188,8 @@ pub fn Editor() -> impl IntoView {
             root_branch: RwSignal::new(root_branch_uid).read_only().into(),
             branches: RwSignal::new(branches),
             statements: Default::default(),
+            report: Default::default(),
+            showing_truth_table: Default::default(),
         };
 
         let mut statements = HashMap::new();
@@ -127,6 +235,12 @@ pub fn Editor() -> impl IntoView {
     });
 
     // effects
+    Effect::watch(
+        move || ctx.focused_statement.track(),
+        move |_, _, _| ctx.report.set(None),
+        false,
+    );
+
     Effect::new(move |_| {
         ctx.focused_statement.with(|x| {
             info!("focused_statement: {:?}", x);
@@ -206,6 +320,13 @@ pub fn Editor() -> impl IntoView {
                     })
                 />
             </div>
+            <Show when=move || ctx.showing_truth_table.get()>
+                <TruthTableView expr=Signal::derive(move || {
+                    ctx.focused_statement
+                        .get()
+                        .and_then(|uid| ctx.statements.read().get(&uid).and_then(|s| s.expr().get()))
+                }) />
+            </Show>
             <div class="flex sticky bottom-0 flex-col justify-center items-center w-full text-white bg-black bg-opacity-40">
                 {move || {
                     current_message
@@ -213,11 +334,20 @@ pub fn Editor() -> impl IntoView {
                         .map(|v| {
                             v.lines()
                                 .map(|l| {
-                                    view! { <p inner_html=l.to_owned() /> }
+                                    view! { <p class="font-mono whitespace-pre">{l.to_owned()}</p> }
                                 })
                                 .collect_view()
                         })
                 }}
+                {move || {
+                    ctx.report
+                        .get()
+                        .map(|v| {
+                            v.lines()
+                                .map(|l| view! { <p>{l.to_owned()}</p> })
+                                .collect_view()
+                        })
+                }}
             </div>
 
         </div>